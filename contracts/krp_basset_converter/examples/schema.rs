@@ -3,7 +3,13 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
-use basset::converter::{ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, NewOwnerResponse, QueryMsg};
+use basset::converter::{
+    AccessModeResponse, AddressListResponse, AdminLogResponse, ConfigResponse, CouncilResponse,
+    EquivalentPairsResponse, ExchangeRateResponse, ExecuteMsg, InstantiateMsg, InvariantsResponse,
+    MigrateMsg, NewOwnerResponse, ProposalResponse, ProposalsResponse, QueryMsg,
+    RedemptionTicketResponse, RedemptionTicketsResponse, RolesResponse, SimulationResponse,
+    StrategyResponse, SudoMsg,
+};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -15,6 +21,21 @@ fn main() {
     export_schema(&schema_for!(QueryMsg), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
     export_schema(&schema_for!(MigrateMsg), &out_dir);
+    export_schema(&schema_for!(SudoMsg), &out_dir);
     export_schema(&schema_for!(ConfigResponse), &out_dir);
     export_schema(&schema_for!(NewOwnerResponse), &out_dir);
+    export_schema(&schema_for!(AccessModeResponse), &out_dir);
+    export_schema(&schema_for!(AddressListResponse), &out_dir);
+    export_schema(&schema_for!(RolesResponse), &out_dir);
+    export_schema(&schema_for!(ExchangeRateResponse), &out_dir);
+    export_schema(&schema_for!(SimulationResponse), &out_dir);
+    export_schema(&schema_for!(RedemptionTicketResponse), &out_dir);
+    export_schema(&schema_for!(RedemptionTicketsResponse), &out_dir);
+    export_schema(&schema_for!(StrategyResponse), &out_dir);
+    export_schema(&schema_for!(InvariantsResponse), &out_dir);
+    export_schema(&schema_for!(CouncilResponse), &out_dir);
+    export_schema(&schema_for!(ProposalResponse), &out_dir);
+    export_schema(&schema_for!(ProposalsResponse), &out_dir);
+    export_schema(&schema_for!(AdminLogResponse), &out_dir);
+    export_schema(&schema_for!(EquivalentPairsResponse), &out_dir);
 }
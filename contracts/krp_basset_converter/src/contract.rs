@@ -2,20 +2,69 @@
 use cosmwasm_std::entry_point;
 
 use crate::state::{
-    read_config, read_new_owner, store_config, store_new_owner, Config, NewOwnerAddr,
+    append_admin_log, create_proposal, is_equivalent_converter, is_listed_address, is_role_member,
+    read_access_mode, read_admin_log, read_backing, read_config, read_conversion_mode,
+    read_council, read_council_epoch, read_custody, read_cw20_underlying, read_equivalent_pair,
+    read_equivalent_pairs, read_exchange_rate, read_executing_proposal, read_ibc_redemption,
+    read_listed_addresses, read_new_owner, read_paused, read_pending_conversion,
+    read_pending_harvest, read_pending_ibc_redemption, read_price_feed, read_proposal,
+    read_proposals, read_queue_position, read_rate_last_updated, read_rate_oracle,
+    read_redemption_queue, read_redemption_ticket, read_redemption_tickets_from, read_role_members,
+    read_rounding_deficit, read_rounding_mode, read_strategy, read_strategy_principal,
+    read_supply_check, read_tokenfactory_basset, read_user_tickets, remove_equivalent_pair,
+    remove_ibc_redemption, remove_listed_address, remove_pending_conversion,
+    remove_pending_harvest, remove_pending_ibc_redemption, remove_redemption_ticket,
+    remove_role_member, remove_supply_check, store_access_mode, store_backing, store_config,
+    store_conversion_mode, store_council, store_council_epoch, store_custody,
+    store_cw20_underlying, store_equivalent_pair, store_exchange_rate, store_executing_proposal,
+    store_ibc_redemption, store_listed_address, store_new_owner, store_paused,
+    store_pending_conversion, store_pending_harvest, store_pending_ibc_redemption,
+    store_price_feed, store_proposal, store_rate_last_updated, store_rate_oracle,
+    store_redemption_queue, store_redemption_ticket, store_role_member, store_rounding_deficit,
+    store_rounding_mode, store_strategy, store_strategy_principal, store_supply_check,
+    store_tokenfactory_basset, AdminLogEntry, Config, CouncilConfig, IbcRedemption, NewOwnerAddr,
+    PendingConversion, PendingHarvest, PriceFeedConfig, Proposal, RedemptionQueue,
+    RedemptionTicket, Strategy, SupplyCheck, TokenFactoryBasset, PREFIX_ALLOWLIST, PREFIX_DENYLIST,
 };
 
 use basset::converter::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, NewOwnerResponse, QueryMsg,
+    AccessMode, AccessModeResponse, AddressListResponse, AdminLogEntryResponse, AdminLogResponse,
+    ConfigResponse, ConversionMode, Council, CouncilResponse, CustodyCw20HookMsg, Cw20HookMsg,
+    DenomOrCw20, EquivalentPairsResponse, ExchangeRateResponse, ExecuteMsg, IbcLifecycleComplete,
+    IbcTransferInfo, InstantiateMsg, InvariantsResponse, MigrateMsg, NewOwnerResponse, PriceFeed,
+    ProposalResponse, ProposalStatus, ProposalsResponse, QueryMsg, RedemptionTicketResponse,
+    RedemptionTicketsResponse, Role, RoleMembers, RolesResponse, RoundingMode, SimulationResponse,
+    StrategyConfig, StrategyExecuteMsg, StrategyResponse, SudoMsg, CONVERT_DIRECTION_KEY,
+    CONVERT_DUST_KEY, CONVERT_EVENT, CONVERT_FEE_KEY, CONVERT_INPUT_AMOUNT_KEY,
+    CONVERT_OUTPUT_AMOUNT_KEY, CONVERT_PAIR_KEY, CONVERT_RECIPIENT_KEY, CONVERT_SENDER_KEY,
+    DIRECTION_TO_BASSET, DIRECTION_TO_NATIVE,
 };
 use cosmwasm_std::{
-    from_json, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env,
-    MessageInfo, Response, StdError, StdResult, Uint128, WasmMsg,
+    from_json, to_json_binary, to_json_string, Addr, BankMsg, Binary, CanonicalAddr, Coin,
+    CosmosMsg, Deps, DepsMut, Env, Event, MessageInfo, Reply, Response, StdError, StdResult,
+    Storage, SubMsg, SubMsgResult, Timestamp, Uint128, WasmMsg,
 };
 
-use crate::math::{convert_to_basset_decimals, convert_to_denom_decimals};
-use crate::querier::query_decimals;
+use crate::ibc::{transfer_sequence, transfer_with_callback};
+use crate::math::{
+    apply_exchange_rate, assert_supported_decimals, convert_to_basset_decimals,
+    convert_to_denom_decimals, remove_exchange_rate, required_backing,
+};
+use crate::querier::{
+    query_converter_config, query_cw20_balance, query_decimals, query_price, query_strategy_value,
+    query_token_info,
+};
+use basset::common::optional_addr_validate;
+use basset::tokenfactory::{tokenfactory_denom, TokenFactoryMsg};
+use cosmwasm_bignumber::{Decimal256, Uint256};
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use serde::Serialize;
+
+pub const CONVERT_FOR_RECIPIENT_REPLY_ID: u64 = 1;
+pub const VERIFY_SUPPLY_REPLY_ID: u64 = 2;
+pub const IBC_TRANSFER_REPLY_ID: u64 = 3;
+pub const HARVEST_REPLY_ID: u64 = 4;
+pub const MAX_BATCH_RECIPIENTS: usize = 50;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -23,7 +72,7 @@ pub fn instantiate(
     _env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
+) -> StdResult<Response<TokenFactoryMsg>> {
     // cannot register the token at the inistantiation
     // because for the basset token contract, converter needs to be minter.
     let conf = Config {
@@ -42,13 +91,16 @@ pub fn instantiate(
         },
     )?;
 
-
-
     Ok(Response::default())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<Response<TokenFactoryMsg>> {
     match msg {
         ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
         ExecuteMsg::RegisterTokens {
@@ -57,260 +109,3475 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             denom_decimals,
         } => register_tokens(
             deps,
+            env,
             info,
             basset_token_address,
             native_denom,
             denom_decimals,
         ),
-        ExecuteMsg::ConvertNativeToBasset {} => execute_convert_to_basset(deps, env, info),
+        ExecuteMsg::RegisterCw20Tokens {
+            basset_token_address,
+            underlying_token,
+        } => register_cw20_tokens(deps, env, info, basset_token_address, underlying_token),
+        ExecuteMsg::RegisterTokenFactoryTokens {
+            subdenom,
+            basset_decimals,
+            native_denom,
+            denom_decimals,
+        } => register_tokenfactory_tokens(
+            deps,
+            env,
+            info,
+            subdenom,
+            basset_decimals,
+            native_denom,
+            denom_decimals,
+        ),
+        ExecuteMsg::ConvertNativeToBasset {
+            recipient,
+            min_output,
+            deadline,
+        } => {
+            let limits = ConversionLimits {
+                min_output,
+                deadline,
+            };
+            match recipient {
+                // the second leg of a conversion for a recipient is executed by the converter itself
+                Some(recipient) if info.sender != env.contract.address => {
+                    convert_for_recipient(deps, env, info, recipient, limits)
+                }
+                _ => execute_convert_to_basset(deps, env, info, recipient, limits),
+            }
+        }
+        ExecuteMsg::ConvertBassetCoinsToNative {
+            ibc,
+            min_output,
+            deadline,
+        } => {
+            let limits = ConversionLimits {
+                min_output,
+                deadline,
+            };
+            convert_basset_coins_to_native(deps, env, info, ibc, limits)
+        }
         ExecuteMsg::SetOwner { new_owner_addr } => {
             let api = deps.api;
-            set_new_owner(deps, info, api.addr_validate(&new_owner_addr)?)
+            set_new_owner(deps, env, info, api.addr_validate(&new_owner_addr)?)
+        }
+        ExecuteMsg::AcceptOwnership {} => accept_ownership(deps, env, info),
+        ExecuteMsg::UpdateAccessMode { mode } => update_access_mode(deps, env, info, mode),
+        ExecuteMsg::UpdateAllowlist { add, remove } => {
+            update_address_list(deps, env, info, PREFIX_ALLOWLIST, add, remove)
+        }
+        ExecuteMsg::UpdateDenylist { add, remove } => {
+            update_address_list(deps, env, info, PREFIX_DENYLIST, add, remove)
+        }
+        ExecuteMsg::GrantRole { role, address } => grant_role(deps, env, info, role, address),
+        ExecuteMsg::RevokeRole { role, address } => revoke_role(deps, env, info, role, address),
+        ExecuteMsg::Pause {} => set_paused(deps, env, info, true),
+        ExecuteMsg::Unpause {} => set_paused(deps, env, info, false),
+        ExecuteMsg::SweepSurplus { recipient } => sweep_surplus(deps, env, info, recipient),
+        ExecuteMsg::RecoverTokens {
+            denom_or_cw20,
+            amount,
+            recipient,
+        } => recover_tokens(deps, env, info, denom_or_cw20, amount, recipient),
+        ExecuteMsg::SetCustody { custody } => set_custody(deps, env, info, custody),
+        ExecuteMsg::ConvertAndDepositCollateral { custody } => {
+            convert_and_deposit_collateral(deps, env, info, custody)
+        }
+        ExecuteMsg::UpdateExchangeRate { exchange_rate } => {
+            update_exchange_rate(deps, env, info, exchange_rate)
+        }
+        ExecuteMsg::SetRateOracle { oracle } => set_rate_oracle(deps, env, info, oracle),
+        ExecuteMsg::SetPriceFeed { price_feed } => set_price_feed(deps, env, info, price_feed),
+        ExecuteMsg::UpdateRoundingMode { rounding_mode } => {
+            update_rounding_mode(deps, env, info, rounding_mode)
+        }
+        ExecuteMsg::UpdateConversionMode { mode } => update_conversion_mode(deps, env, info, mode),
+        ExecuteMsg::ClaimRedemption { id } => claim_redemption(deps, env, info, id),
+        ExecuteMsg::CancelRedemption { id } => cancel_redemption(deps, info, id),
+        ExecuteMsg::SetStrategy { strategy } => set_strategy(deps, env, info, strategy),
+        ExecuteMsg::AllocateToStrategy { amount } => allocate_to_strategy(deps, env, info, amount),
+        ExecuteMsg::WithdrawFromStrategy { amount } => {
+            withdraw_from_strategy(deps, env, info, amount)
+        }
+        ExecuteMsg::HarvestStrategyYield { recipient } => {
+            harvest_strategy_yield(deps, env, info, recipient)
+        }
+        ExecuteMsg::BatchConvertNativeToBasset { recipients } => {
+            batch_convert_to_basset(deps, env, info, recipients)
+        }
+        ExecuteMsg::SetEquivalentPair {
+            basset_token,
+            converter,
+        } => set_equivalent_pair(deps, env, info, basset_token, converter),
+        ExecuteMsg::MintSwappedBasset {
+            recipient,
+            min_output,
+            deadline,
+        } => mint_swapped_basset(
+            deps,
+            env,
+            info,
+            recipient,
+            ConversionLimits {
+                min_output,
+                deadline,
+            },
+        ),
+        ExecuteMsg::SetCouncil { council } => set_council(deps, env, info, council),
+        ExecuteMsg::Propose { action } => propose(deps, env, info, *action),
+        ExecuteMsg::Approve { id } => vote(deps, env, info, id, true),
+        ExecuteMsg::Reject { id } => vote(deps, env, info, id, false),
+        ExecuteMsg::Execute { id } => execute_proposal(deps, env, info, id),
+    }
+}
+
+/// The owner holds every role, other addresses need an explicit grant.
+/// With a council set, the admin role is only held by executed proposals.
+pub(crate) fn has_role(deps: Deps, sender: &Addr, role: Role) -> StdResult<bool> {
+    let config = read_config(deps.storage)?;
+    let sender_raw = deps.api.addr_canonicalize(sender.as_str())?;
+    if let Some(council) = read_council(deps.storage)? {
+        if sender_raw == council.executor {
+            return Ok(true);
+        }
+        if role == Role::Admin {
+            return Ok(false);
         }
-        ExecuteMsg::AcceptOwnership {} => accept_ownership(deps, info),
     }
+    Ok(sender_raw == config.owner || is_role_member(deps.storage, role, &sender_raw)?)
+}
+
+pub(crate) fn assert_role(deps: Deps, sender: &Addr, role: Role) -> StdResult<()> {
+    if !has_role(deps, sender, role)? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+    Ok(())
+}
+
+/// Appends an admin action with the json encoded values before and after it to the admin log.
+fn log_admin_action<O: Serialize, N: Serialize>(
+    deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    action: &str,
+    old_value: &O,
+    new_value: &N,
+) -> StdResult<()> {
+    // actions of the council run with the converter as sender, record who approved them
+    let proposal = match read_executing_proposal(deps.storage)? {
+        Some(id) if *sender == env.contract.address => Some(read_proposal(deps.storage, id)?),
+        _ => None,
+    };
+    append_admin_log(
+        deps.storage,
+        AdminLogEntry {
+            id: 0,
+            sender: deps.api.addr_canonicalize(sender.as_str())?,
+            action: action.to_string(),
+            old_value: to_json_string(old_value)?,
+            new_value: to_json_string(new_value)?,
+            time: env.block.time.seconds(),
+            proposal_id: proposal.as_ref().map(|proposal| proposal.id),
+            approvers: proposal
+                .map(|proposal| proposal.approvals)
+                .unwrap_or_default(),
+        },
+    )
+}
+
+pub fn grant_role(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    assert_role(deps.as_ref(), &info.sender, Role::Admin)?;
+
+    let old_members = read_role_members(deps.storage, deps.api, role)?;
+    let address_raw = deps.api.addr_canonicalize(&address)?;
+    store_role_member(deps.storage, role, &address_raw)?;
+    let new_members = read_role_members(deps.storage, deps.api, role)?;
+    log_admin_action(
+        deps,
+        &env,
+        &info.sender,
+        "grant_role",
+        &old_members,
+        &new_members,
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "grant_role"),
+        ("role", role.as_str()),
+        ("address", &address),
+    ]))
+}
+
+pub fn revoke_role(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    assert_role(deps.as_ref(), &info.sender, Role::Admin)?;
+
+    let old_members = read_role_members(deps.storage, deps.api, role)?;
+    let address_raw = deps.api.addr_canonicalize(&address)?;
+    remove_role_member(deps.storage, role, &address_raw);
+    let new_members = read_role_members(deps.storage, deps.api, role)?;
+    log_admin_action(
+        deps,
+        &env,
+        &info.sender,
+        "revoke_role",
+        &old_members,
+        &new_members,
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "revoke_role"),
+        ("role", role.as_str()),
+        ("address", &address),
+    ]))
+}
+
+pub fn set_paused(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    paused: bool,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    // guardians can stop the converter but only an admin can resume it
+    if !(paused && has_role(deps.as_ref(), &info.sender, Role::Guardian)?) {
+        assert_role(deps.as_ref(), &info.sender, Role::Admin)?;
+    }
+
+    let was_paused = read_paused(deps.storage)?;
+    store_paused(deps.storage, paused)?;
+
+    let action = if paused { "pause" } else { "unpause" };
+    log_admin_action(deps, &env, &info.sender, action, &was_paused, &paused)?;
+    Ok(Response::new().add_attributes(vec![("action", action)]))
 }
 
 pub fn set_new_owner(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     new_owner_addr: Addr,
-) -> StdResult<Response> {
-    let config = read_config(deps.as_ref().storage)?;
+) -> StdResult<Response<TokenFactoryMsg>> {
+    assert_role(deps.as_ref(), &info.sender, Role::Admin)?;
     let mut new_owner = read_new_owner(deps.as_ref().storage)?;
-    let sender_raw = deps.api.addr_canonicalize(&info.sender.to_string())?;
-    if sender_raw != config.owner {
-        return Err(StdError::generic_err("unauthorized"));
-    }
-    new_owner.new_owner_addr = deps.api.addr_canonicalize(&new_owner_addr.to_string())?;
+    let old_new_owner = deps.api.addr_humanize(&new_owner.new_owner_addr)?;
+    new_owner.new_owner_addr = deps.api.addr_canonicalize(new_owner_addr.as_str())?;
     store_new_owner(deps.storage, &new_owner)?;
+    log_admin_action(
+        deps,
+        &env,
+        &info.sender,
+        "set_owner",
+        &old_new_owner,
+        &new_owner_addr,
+    )?;
 
     Ok(Response::default())
 }
 
-pub fn accept_ownership(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
+pub fn accept_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> StdResult<Response<TokenFactoryMsg>> {
     let new_owner = read_new_owner(deps.as_ref().storage)?;
-    let sender_raw = deps.api.addr_canonicalize(&info.sender.to_string())?;
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
     let mut config = read_config(deps.as_ref().storage)?;
     if sender_raw != new_owner.new_owner_addr {
         return Err(StdError::generic_err("unauthorized"));
     }
 
+    let old_owner = deps.api.addr_humanize(&config.owner)?;
     config.owner = new_owner.new_owner_addr;
     store_config(deps.storage).save(&config)?;
+    log_admin_action(
+        deps,
+        &env,
+        &info.sender,
+        "accept_ownership",
+        &old_owner,
+        &info.sender,
+    )?;
 
     Ok(Response::default())
 }
 
-/// CW20 token receive handler.
-pub fn receive_cw20(
+pub fn set_council(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    cw20_msg: Cw20ReceiveMsg,
-) -> StdResult<Response> {
-    let contract_addr = info.sender.clone();
-    match from_json(&cw20_msg.msg) {
-        Ok(Cw20HookMsg::ConvertBassetToNative {}) => {
-            // only basset beth token contract can execute this message
-            let conf = read_config(deps.storage)?;
-            if deps.api.addr_canonicalize(contract_addr.as_str())?
-                != conf.basset_token_address.unwrap()
-            {
-                return Err(StdError::generic_err("unauthorized"));
+    council: Option<Council>,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    assert_role(deps.as_ref(), &info.sender, Role::Admin)?;
+
+    let old_council = query_council(deps.as_ref())?.council;
+    let new_council = council.clone();
+    let council = match council {
+        Some(council) => {
+            let mut members: Vec<CanonicalAddr> = vec![];
+            for member in council.members.iter() {
+                let member_raw = deps.api.addr_canonicalize(member)?;
+                if members.contains(&member_raw) {
+                    return Err(StdError::generic_err(format!(
+                        "duplicate council member {}",
+                        member
+                    )));
+                }
+                members.push(member_raw);
             }
-            execute_convert_to_native(deps, env, info, cw20_msg.amount, cw20_msg.sender)
+            if council.threshold == 0 || council.threshold as usize > members.len() {
+                return Err(StdError::generic_err(
+                    "threshold must be between one and the number of members",
+                ));
+            }
+            if council.voting_period == 0 {
+                return Err(StdError::generic_err("voting period must be positive"));
+            }
+            Some(CouncilConfig {
+                members,
+                threshold: council.threshold,
+                voting_period: council.voting_period,
+                executor: deps.api.addr_canonicalize(env.contract.address.as_str())?,
+            })
         }
-        Err(err) => Err(err),
+        None => None,
+    };
+    store_council(deps.storage, council)?;
+    // proposals of the previous council can no longer be voted on or executed
+    let epoch = read_council_epoch(deps.storage)?;
+    store_council_epoch(deps.storage, epoch + 1)?;
+    log_admin_action(
+        deps,
+        &env,
+        &info.sender,
+        "set_council",
+        &old_council,
+        &new_council,
+    )?;
+
+    Ok(Response::new().add_attributes(vec![("action", "set_council")]))
+}
+
+fn assert_council_member(deps: Deps, sender: &Addr) -> StdResult<(CouncilConfig, CanonicalAddr)> {
+    let council =
+        read_council(deps.storage)?.ok_or_else(|| StdError::generic_err("no council is set"))?;
+    let sender_raw = deps.api.addr_canonicalize(sender.as_str())?;
+    if !council.members.contains(&sender_raw) {
+        return Err(StdError::generic_err("unauthorized"));
     }
+    Ok((council, sender_raw))
 }
 
-pub fn register_tokens(
+fn assert_current_council(deps: Deps, proposal: &Proposal) -> StdResult<()> {
+    if proposal.council_epoch != read_council_epoch(deps.storage)? {
+        return Err(StdError::generic_err(
+            "proposal was made under a replaced council",
+        ));
+    }
+    Ok(())
+}
+
+/// Only role gated actions can go through the council, the rest have no use for its sender.
+fn is_council_action(action: &ExecuteMsg) -> bool {
+    !matches!(
+        action,
+        ExecuteMsg::Receive(_)
+            | ExecuteMsg::ConvertNativeToBasset { .. }
+            | ExecuteMsg::BatchConvertNativeToBasset { .. }
+            | ExecuteMsg::ConvertBassetCoinsToNative { .. }
+            | ExecuteMsg::MintSwappedBasset { .. }
+            | ExecuteMsg::ConvertAndDepositCollateral { .. }
+            | ExecuteMsg::AcceptOwnership {}
+            | ExecuteMsg::ClaimRedemption { .. }
+            | ExecuteMsg::CancelRedemption { .. }
+            | ExecuteMsg::Propose { .. }
+            | ExecuteMsg::Approve { .. }
+            | ExecuteMsg::Reject { .. }
+            | ExecuteMsg::Execute { .. }
+    )
+}
+
+pub fn propose(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    basset_token_address: String,
-    native_denom: String,
-    denom_decimals: u8,
-) -> StdResult<Response> {
-    let mut config = read_config(deps.storage)?;
-
-    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
-        return Err(StdError::generic_err("unauthorized"));
+    action: ExecuteMsg,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    let (council, sender_raw) = assert_council_member(deps.as_ref(), &info.sender)?;
+    if !is_council_action(&action) {
+        return Err(StdError::generic_err("action cannot be proposed"));
     }
 
-    // if the token contract is  already register we cannot change the address
-    if config.basset_token_address.is_none() {
-        config.basset_token_address = Some(deps.api.addr_canonicalize(&basset_token_address)?);
+    let status = if council.threshold == 1 {
+        ProposalStatus::Passed
+    } else {
+        ProposalStatus::Open
+    };
+    let council_epoch = read_council_epoch(deps.storage)?;
+    let proposal = create_proposal(
+        deps.storage,
+        Proposal {
+            id: 0,
+            proposer: sender_raw.clone(),
+            action,
+            approvals: vec![sender_raw],
+            rejections: vec![],
+            expires_at: env.block.time.seconds() + council.voting_period,
+            status,
+            council_epoch,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "propose".to_string()),
+        ("proposal_id", proposal.id.to_string()),
+    ]))
+}
+
+pub fn vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    approve: bool,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    let (council, sender_raw) = assert_council_member(deps.as_ref(), &info.sender)?;
+    let mut proposal = read_proposal(deps.storage, id)?;
+    assert_current_council(deps.as_ref(), &proposal)?;
+    if proposal.status != ProposalStatus::Open || env.block.time.seconds() >= proposal.expires_at {
+        return Err(StdError::generic_err("proposal is not open for voting"));
+    }
+    if proposal.approvals.contains(&sender_raw) || proposal.rejections.contains(&sender_raw) {
+        return Err(StdError::generic_err("already voted"));
     }
 
-    // if the token contract is  already register we cannot change the address
-    if config.native_denom.is_none() {
-        config.native_denom = Some(native_denom);
+    if approve {
+        proposal.approvals.push(sender_raw);
+        if proposal.approvals.len() >= council.threshold as usize {
+            proposal.status = ProposalStatus::Passed;
+        }
+    } else {
+        proposal.rejections.push(sender_raw);
+        // the threshold can no longer be reached
+        if proposal.rejections.len() > council.members.len() - council.threshold as usize {
+            proposal.status = ProposalStatus::Rejected;
+        }
     }
+    store_proposal(deps.storage, &proposal)?;
 
-    if config.denom_decimals.is_none() {
-        config.denom_decimals = Some(denom_decimals);
+    let action = if approve { "approve" } else { "reject" };
+    Ok(Response::new().add_attributes(vec![
+        ("action", action.to_string()),
+        ("proposal_id", id.to_string()),
+    ]))
+}
+
+pub fn execute_proposal(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    assert_council_member(deps.as_ref(), &info.sender)?;
+    let mut proposal = read_proposal(deps.storage, id)?;
+    assert_current_council(deps.as_ref(), &proposal)?;
+    if proposal.status != ProposalStatus::Passed {
+        return Err(StdError::generic_err("proposal has not passed"));
+    }
+    if env.block.time.seconds() >= proposal.expires_at {
+        return Err(StdError::generic_err("proposal has expired"));
     }
 
-    store_config(deps.storage).save(&config)?;
+    proposal.status = ProposalStatus::Executed;
+    store_proposal(deps.storage, &proposal)?;
 
-    Ok(Response::new().add_attributes(vec![("action", "register_token_contracts")]))
+    // the action runs with the converter as sender, which holds the council's roles
+    let council_info = MessageInfo {
+        sender: env.contract.address.clone(),
+        funds: vec![],
+    };
+    store_executing_proposal(deps.storage, Some(id))?;
+    let res = execute(deps.branch(), env, council_info, proposal.action)?;
+    store_executing_proposal(deps.storage, None)?;
+
+    Ok(res.add_attributes(vec![
+        ("council_action", "execute".to_string()),
+        ("proposal_id", id.to_string()),
+    ]))
 }
 
-pub(crate) fn execute_convert_to_basset(
+pub fn update_access_mode(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-) -> StdResult<Response> {
-    let config = read_config(deps.storage)?;
+    mode: AccessMode,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    assert_role(deps.as_ref(), &info.sender, Role::Operator)?;
 
-    if config.native_denom.is_none() || config.native_denom.is_none() {
-        return Err(StdError::generic_err(
-            "native denom must be registered first",
-        ));
+    let old_mode = read_access_mode(deps.storage)?;
+    store_access_mode(deps.storage, &mode)?;
+    log_admin_action(
+        deps,
+        &env,
+        &info.sender,
+        "update_access_mode",
+        &old_mode,
+        &mode,
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_access_mode"),
+        ("mode", &mode.to_string()),
+    ]))
+}
+
+/// The admin log records the removed addresses as the old value and the added ones as the new.
+pub fn update_address_list(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    prefix: &[u8],
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    assert_role(deps.as_ref(), &info.sender, Role::Operator)?;
+
+    for address in add.iter() {
+        let address_raw = deps.api.addr_canonicalize(address)?;
+        store_listed_address(deps.storage, prefix, &address_raw)?;
+    }
+    for address in remove.iter() {
+        let address_raw = deps.api.addr_canonicalize(address)?;
+        remove_listed_address(deps.storage, prefix, &address_raw);
     }
-    let coin_denom = config.native_denom.unwrap();
 
-    if info.funds.len() != 1 {
-        return Err(StdError::generic_err(
-            "The execute_convert_to_basset function only receives one registered native denom.",
-        ));
+    let action = if prefix == PREFIX_ALLOWLIST {
+        "update_allowlist"
+    } else {
+        "update_denylist"
+    };
+    log_admin_action(deps, &env, &info.sender, action, &remove, &add)?;
+    Ok(Response::new().add_attributes(vec![
+        ("action", action),
+        ("added", &add.len().to_string()),
+        ("removed", &remove.len().to_string()),
+    ]))
+}
+
+fn assert_not_paused(deps: Deps) -> StdResult<()> {
+    if read_paused(deps.storage)? {
+        return Err(StdError::generic_err("conversions are paused"));
     }
+    Ok(())
+}
 
-    let coin = info
-        .funds
-        .iter()
-        .find(|x| x.denom == coin_denom && x.amount > Uint128::zero())
-        .ok_or_else(|| {
-            StdError::generic_err(format!("No {} assets are provided to deposit", coin_denom));
-        });
+/// Checks the conversion access mode. The allowlist applies to the address providing the
+/// funds, the denylist to both that address and the receiver of the converted asset.
+fn assert_access_allowed(deps: Deps, sender: &str, recipient: &str) -> StdResult<()> {
+    match read_access_mode(deps.storage)? {
+        AccessMode::Open => Ok(()),
+        AccessMode::Allowlist => {
+            let sender_raw = deps.api.addr_canonicalize(sender)?;
+            if !is_listed_address(deps.storage, PREFIX_ALLOWLIST, &sender_raw)? {
+                return Err(StdError::generic_err(format!(
+                    "{} is not on the allowlist",
+                    sender
+                )));
+            }
+            Ok(())
+        }
+        AccessMode::Denylist => {
+            for address in [sender, recipient] {
+                let address_raw = deps.api.addr_canonicalize(address)?;
+                if is_listed_address(deps.storage, PREFIX_DENYLIST, &address_raw)? {
+                    return Err(StdError::generic_err(format!(
+                        "{} is on the denylist",
+                        address
+                    )));
+                }
+            }
+            Ok(())
+        }
+    }
+}
 
-    let basset_decimals = query_decimals(
-        deps.as_ref(),
-        deps.api
-            .addr_humanize(config.basset_token_address.as_ref().unwrap())
-            .unwrap(),
-    )?;
+pub fn set_custody(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    custody: String,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    assert_role(deps.as_ref(), &info.sender, Role::Admin)?;
 
-    // should convert to basset decimals
-    let mint_amount = convert_to_basset_decimals(
-        coin.unwrap().amount,
-        basset_decimals,
-        config.denom_decimals.unwrap(),
+    let old_custody = query_config(deps.as_ref())?.custody_contract;
+    let custody_raw = deps.api.addr_canonicalize(&custody)?;
+    store_custody(deps.storage, &custody_raw)?;
+    log_admin_action(
+        deps,
+        &env,
+        &info.sender,
+        "set_custody",
+        &old_custody,
+        &custody,
     )?;
 
-    Ok(Response::new()
-        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: deps
-                .api
-                .addr_humanize(&config.basset_token_address.unwrap())?
-                .to_string(),
-            msg: to_json_binary(&Cw20ExecuteMsg::Mint {
-                recipient: info.sender.to_string(),
-                amount: mint_amount,
-            })?,
-            funds: vec![],
-        }))
-        .add_attributes(vec![
-            ("action", "convert-to-basset"),
-            ("recipient", &info.sender.to_string()),
-            ("minted_amount", &mint_amount.to_string()),
-        ]))
+    Ok(Response::new().add_attributes(vec![("action", "set_custody"), ("custody", &custody)]))
 }
 
-pub(crate) fn execute_convert_to_native(
+pub fn update_exchange_rate(
     deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
-    amount: Uint128,
-    sender: String,
-) -> StdResult<Response> {
-    let config = read_config(deps.storage)?;
-    if config.basset_token_address.is_none() || config.native_denom.is_none() {
+    env: Env,
+    info: MessageInfo,
+    exchange_rate: Decimal256,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let from_oracle = read_rate_oracle(deps.storage)? == Some(sender_raw);
+    if !from_oracle {
+        assert_role(deps.as_ref(), &info.sender, Role::Admin)?;
+    }
+    if exchange_rate.is_zero() {
         return Err(StdError::generic_err(
-            "native or basset token must be registered first",
+            "exchange rate must be greater than zero",
         ));
     }
 
-    let basset_decimals = query_decimals(
-        deps.as_ref(),
-        deps.api
-            .addr_humanize(config.basset_token_address.as_ref().unwrap())
-            .unwrap(),
-    )?;
-
-    // should convert to native decimals
-    let return_amount =
-        convert_to_denom_decimals(amount, basset_decimals, config.denom_decimals.unwrap())?;
+    let old_rate = read_exchange_rate(deps.storage)?;
+    store_exchange_rate(deps.storage, &exchange_rate)?;
+    // the oracle's routine pushes are not admin actions
+    if !from_oracle {
+        log_admin_action(
+            deps,
+            &env,
+            &info.sender,
+            "update_exchange_rate",
+            &old_rate,
+            &exchange_rate,
+        )?;
+    }
 
-    Ok(Response::new()
-        .add_messages(vec![
-            CosmosMsg::Bank(BankMsg::Send {
-                to_address: sender.clone(),
-                amount: vec![Coin {
-                    amount: return_amount,
-                    denom: config.native_denom.unwrap(),
-                }],
-            }),
-            CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: deps
-                    .api
-                    .addr_humanize(&config.basset_token_address.unwrap())?
-                    .to_string(),
-                msg: to_json_binary(&Cw20ExecuteMsg::Burn { amount })?,
-                funds: vec![],
-            }),
-        ])
-        .add_attributes(vec![
-            ("action", "convert-to-native"),
-            ("recipient", &sender),
-            ("return_amount", &return_amount.to_string()),
-            ("burn_amount", &amount.to_string()),
-        ]))
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_exchange_rate"),
+        ("exchange_rate", &exchange_rate.to_string()),
+    ]))
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
-        QueryMsg::NewOwner {} => to_json_binary(&query_new_owner(deps)?),
-    }
-}
+pub fn set_rate_oracle(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    oracle: Option<String>,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    assert_role(deps.as_ref(), &info.sender, Role::Admin)?;
 
-pub fn query_new_owner(deps: Deps) -> StdResult<NewOwnerResponse> {
-    let new_owner = read_new_owner(deps.storage)?;
-    Ok(NewOwnerResponse {
-        new_owner: deps
-            .api
-            .addr_humanize(&new_owner.new_owner_addr)?
-            .to_string(),
-    })
+    let old_oracle = query_exchange_rate(deps.as_ref())?.oracle;
+    let oracle_raw = match &oracle {
+        Some(oracle) => Some(deps.api.addr_canonicalize(oracle)?),
+        None => None,
+    };
+    store_rate_oracle(deps.storage, oracle_raw)?;
+    log_admin_action(
+        deps,
+        &env,
+        &info.sender,
+        "set_rate_oracle",
+        &old_oracle,
+        &oracle,
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_rate_oracle"),
+        ("oracle", oracle.as_deref().unwrap_or("none")),
+    ]))
 }
 
-pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
-    let config: Config = read_config(deps.storage)?;
-    let basset_token = if config.basset_token_address.is_some() {
-        Some(
-            deps.api
-                .addr_humanize(&config.basset_token_address.unwrap())?
-                .to_string(),
-        )
-    } else {
-        None
+pub fn set_price_feed(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    price_feed: Option<PriceFeed>,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    assert_role(deps.as_ref(), &info.sender, Role::Admin)?;
+
+    let old_price_feed = query_exchange_rate(deps.as_ref())?.price_feed;
+    let price_feed_raw = match &price_feed {
+        Some(price_feed) => {
+            if price_feed.max_age == 0 {
+                return Err(StdError::generic_err(
+                    "price feed max age must be greater than zero",
+                ));
+            }
+            Some(PriceFeedConfig {
+                oracle: deps.api.addr_canonicalize(&price_feed.oracle)?,
+                base: price_feed.base.clone(),
+                quote: price_feed.quote.clone(),
+                max_age: price_feed.max_age,
+                max_deviation: price_feed.max_deviation,
+            })
+        }
+        None => None,
     };
-    let native_denom = if config.native_denom.is_some() {
-        Some(config.native_denom.unwrap())
-    } else {
+    store_price_feed(deps.storage, price_feed_raw)?;
+    log_admin_action(
+        deps,
+        &env,
+        &info.sender,
+        "set_price_feed",
+        &old_price_feed,
+        &price_feed,
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_price_feed"),
+        (
+            "oracle",
+            price_feed
+                .as_ref()
+                .map_or("none", |feed| feed.oracle.as_str()),
+        ),
+    ]))
+}
+
+pub fn update_rounding_mode(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    rounding_mode: RoundingMode,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    assert_role(deps.as_ref(), &info.sender, Role::Admin)?;
+    let old_rounding_mode = read_rounding_mode(deps.storage)?;
+    store_rounding_mode(deps.storage, &rounding_mode)?;
+    log_admin_action(
+        deps,
+        &env,
+        &info.sender,
+        "update_rounding_mode",
+        &old_rounding_mode,
+        &rounding_mode,
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_rounding_mode"),
+        ("rounding_mode", &rounding_mode.to_string()),
+    ]))
+}
+
+pub fn update_conversion_mode(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    mode: ConversionMode,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    assert_role(deps.as_ref(), &info.sender, Role::Admin)?;
+    let old_mode = read_conversion_mode(deps.storage)?;
+    store_conversion_mode(deps.storage, &mode)?;
+    log_admin_action(
+        deps,
+        &env,
+        &info.sender,
+        "update_conversion_mode",
+        &old_mode,
+        &mode,
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_conversion_mode"),
+        ("mode", &mode.to_string()),
+    ]))
+}
+
+fn assert_can_mint(deps: Deps) -> StdResult<()> {
+    if !read_conversion_mode(deps.storage)?.can_mint() {
+        return Err(StdError::generic_err(
+            "conversions to basset are disabled for this pair",
+        ));
+    }
+    Ok(())
+}
+
+fn assert_can_redeem(deps: Deps) -> StdResult<()> {
+    if !read_conversion_mode(deps.storage)?.can_redeem() {
+        return Err(StdError::generic_err(
+            "conversions to native are disabled for this pair",
+        ));
+    }
+    Ok(())
+}
+
+/// Takes the exchange rate from the price feed, if one is configured. Prices older than
+/// the max age or too far from the last accepted rate fail the conversion.
+fn refresh_exchange_rate(deps: DepsMut, env: &Env) -> StdResult<()> {
+    let price_feed = match read_price_feed(deps.storage)? {
+        Some(price_feed) => price_feed,
+        None => return Ok(()),
+    };
+
+    let price = query_price(
+        deps.as_ref(),
+        deps.api.addr_humanize(&price_feed.oracle)?,
+        price_feed.base,
+        price_feed.quote,
+    )?;
+    if price.rate.is_zero() {
+        return Err(StdError::generic_err(
+            "oracle price must be greater than zero",
+        ));
+    }
+
+    let last_updated = price.last_updated_base.min(price.last_updated_quote);
+    if env.block.time.seconds().saturating_sub(last_updated) > price_feed.max_age {
+        return Err(StdError::generic_err(format!(
+            "oracle price is stale, last updated at {}",
+            last_updated
+        )));
+    }
+
+    let last_rate = read_exchange_rate(deps.storage)?;
+    let deviation = if price.rate > last_rate {
+        price.rate - last_rate
+    } else {
+        last_rate - price.rate
+    };
+    if deviation > last_rate * price_feed.max_deviation {
+        return Err(StdError::generic_err(format!(
+            "oracle price {} deviates too much from the last exchange rate {}",
+            price.rate, last_rate
+        )));
+    }
+
+    store_exchange_rate(deps.storage, &price.rate)?;
+    store_rate_last_updated(deps.storage, last_updated)
+}
+
+/// Sends the native balance that is not needed to back the basset supply.
+pub fn sweep_surplus(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    assert_role(deps.as_ref(), &info.sender, Role::Treasurer)?;
+    let recipient = deps.api.addr_validate(&recipient)?;
+
+    let config = read_config(deps.storage)?;
+    let denom_decimals = match (config.native_denom.as_ref(), config.denom_decimals) {
+        (Some(_), Some(denom_decimals)) if basset_registered(deps.as_ref(), &config)? => {
+            denom_decimals
+        }
+        _ => {
+            return Err(StdError::generic_err(
+                "native or basset token must be registered first",
+            ))
+        }
+    };
+
+    let (supply, basset_decimals) = basset_supply(deps.as_ref(), &config)?;
+    let required_backing = required_backing(
+        supply,
+        basset_decimals,
+        denom_decimals,
+        read_exchange_rate(deps.storage)?,
+    )?;
+    // the ledger also holds the dust conversions kept, sweeping it would leave reserves missing
+    let backing = read_backing(deps.storage)?.max(required_backing);
+    let reserve = reserve_asset(deps.as_ref(), &config)?;
+    let balance = reserve_balance(deps.as_ref(), &env, &reserve)?;
+
+    // reserves allocated to a strategy back the supply as well, but only the
+    // converter balance can be swept
+    let reserves = balance.checked_add(read_strategy_principal(deps.storage)?)?;
+    let surplus = reserves.saturating_sub(backing).min(balance);
+    if surplus.is_zero() {
+        return Err(StdError::generic_err("no surplus reserves to sweep"));
+    }
+
+    log_admin_action(
+        deps,
+        &env,
+        &info.sender,
+        "sweep_surplus",
+        &reserve,
+        &(surplus, &recipient),
+    )?;
+
+    Ok(Response::new()
+        .add_message(transfer_msg(&reserve, recipient.as_str(), surplus)?)
+        .add_attributes(vec![
+            ("action", "sweep_surplus"),
+            ("recipient", recipient.as_str()),
+            ("required_backing", &backing.to_string()),
+            ("swept_amount", &surplus.to_string()),
+        ]))
+}
+
+/// Basset held beyond the escrow of queued redemptions was sent by plain transfer.
+fn assert_basset_recoverable(deps: Deps, balance: Uint128, amount: Uint128) -> StdResult<()> {
+    let escrowed = read_redemption_queue(deps.storage)?.escrowed;
+    let recoverable = balance.saturating_sub(escrowed);
+    if amount > recoverable {
+        return Err(StdError::generic_err(format!(
+            "only {} basset is recoverable, {} is escrowed for queued redemptions",
+            recoverable, escrowed
+        )));
+    }
+    Ok(())
+}
+
+/// Returns assets that are not part of the reserves. Basset held by the converter beyond the
+/// escrow of queued redemptions comes from plain transfers, so it is either burned or returned
+/// without touching the reserves.
+pub fn recover_tokens(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    denom_or_cw20: DenomOrCw20,
+    amount: Uint128,
+    recipient: Option<String>,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    assert_role(deps.as_ref(), &info.sender, Role::Admin)?;
+    if amount.is_zero() {
+        return Err(StdError::generic_err(
+            "recover amount must be greater than zero",
+        ));
+    }
+
+    let config = read_config(deps.storage)?;
+    let recipient = optional_addr_validate(deps.api, recipient)?;
+
+    let message = match &denom_or_cw20 {
+        DenomOrCw20::Native(denom) => {
+            if config.native_denom.as_ref() == Some(denom) {
+                return Err(StdError::generic_err(
+                    "the registered native denom backs the basset supply and cannot be recovered",
+                ));
+            }
+            let is_basset = read_tokenfactory_basset(deps.storage)?
+                .is_some_and(|basset| &basset.denom == denom);
+            if is_basset {
+                let balance = deps
+                    .querier
+                    .query_balance(env.contract.address.clone(), denom.clone())?
+                    .amount;
+                assert_basset_recoverable(deps.as_ref(), balance, amount)?;
+            }
+            match &recipient {
+                Some(recipient) => SubMsg::new(BankMsg::Send {
+                    to_address: recipient.to_string(),
+                    amount: vec![Coin {
+                        amount,
+                        denom: denom.clone(),
+                    }],
+                }),
+                None if is_basset => supply_change_msg(
+                    deps.branch(),
+                    &denom_or_cw20,
+                    Cw20ExecuteMsg::Burn { amount },
+                )?
+                .remove(0),
+                None => {
+                    return Err(StdError::generic_err(
+                        "recipient is required for native denoms other than basset",
+                    ))
+                }
+            }
+        }
+        DenomOrCw20::Cw20(contract_addr) => {
+            let contract_addr = deps.api.addr_validate(contract_addr)?;
+            let contract_raw = deps.api.addr_canonicalize(contract_addr.as_str())?;
+            if read_cw20_underlying(deps.storage)? == Some(contract_raw.clone()) {
+                return Err(StdError::generic_err(
+                    "the registered underlying token backs the basset supply and cannot be recovered",
+                ));
+            }
+            let is_basset = config.basset_token_address == Some(contract_raw);
+            if is_basset {
+                let balance = query_cw20_balance(
+                    deps.as_ref(),
+                    contract_addr.clone(),
+                    env.contract.address.clone(),
+                )?;
+                assert_basset_recoverable(deps.as_ref(), balance, amount)?;
+            }
+            match &recipient {
+                Some(recipient) => SubMsg::new(WasmMsg::Execute {
+                    contract_addr: contract_addr.to_string(),
+                    msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: recipient.to_string(),
+                        amount,
+                    })?,
+                    funds: vec![],
+                }),
+                None if is_basset => supply_change_msg(
+                    deps.branch(),
+                    &DenomOrCw20::Cw20(contract_addr.to_string()),
+                    Cw20ExecuteMsg::Burn { amount },
+                )?
+                .remove(0),
+                None => {
+                    return Err(StdError::generic_err(
+                        "recipient is required for cw20 tokens other than basset",
+                    ))
+                }
+            }
+        }
+    };
+
+    let recipient = recipient.map_or_else(|| "burn".to_string(), |addr| addr.to_string());
+    log_admin_action(
+        deps,
+        &env,
+        &info.sender,
+        "recover_tokens",
+        &denom_or_cw20,
+        &(amount, &recipient),
+    )?;
+
+    let asset = match denom_or_cw20 {
+        DenomOrCw20::Native(denom) => denom,
+        DenomOrCw20::Cw20(contract_addr) => contract_addr,
+    };
+    Ok(Response::new().add_submessage(message).add_attributes(vec![
+        ("action", "recover_tokens"),
+        ("asset", &asset),
+        ("amount", &amount.to_string()),
+        ("recipient", &recipient),
+    ]))
+}
+
+pub fn set_strategy(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    strategy: Option<StrategyConfig>,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    assert_role(deps.as_ref(), &info.sender, Role::Admin)?;
+
+    let strategy_raw = match &strategy {
+        Some(_) if read_cw20_underlying(deps.storage)?.is_some() => {
+            return Err(StdError::generic_err("strategies need a native underlying"))
+        }
+        Some(strategy) => {
+            if strategy.max_allocation > Decimal256::one() {
+                return Err(StdError::generic_err(
+                    "max allocation must not exceed the reserves",
+                ));
+            }
+            Some(Strategy {
+                strategy: deps.api.addr_canonicalize(&strategy.strategy)?,
+                max_allocation: strategy.max_allocation,
+                liquidity_buffer: strategy.liquidity_buffer,
+            })
+        }
+        None => None,
+    };
+    // the allocated reserves must come back before the strategy is replaced
+    let current = read_strategy(deps.storage)?;
+    let replaced = match (&current, &strategy_raw) {
+        (Some(current), Some(new)) => current.strategy != new.strategy,
+        (Some(_), None) => true,
+        _ => false,
+    };
+    if replaced && !read_strategy_principal(deps.storage)?.is_zero() {
+        return Err(StdError::generic_err(
+            "strategy still holds reserves, withdraw them first",
+        ));
+    }
+    let old_strategy = to_strategy_config(deps.as_ref(), current)?;
+    store_strategy(deps.storage, strategy_raw)?;
+    log_admin_action(
+        deps,
+        &env,
+        &info.sender,
+        "set_strategy",
+        &old_strategy,
+        &strategy,
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_strategy"),
+        (
+            "strategy",
+            strategy.as_ref().map_or("none", |s| s.strategy.as_str()),
+        ),
+    ]))
+}
+
+fn to_strategy_config(deps: Deps, strategy: Option<Strategy>) -> StdResult<Option<StrategyConfig>> {
+    strategy
+        .map(|strategy| {
+            Ok(StrategyConfig {
+                strategy: deps.api.addr_humanize(&strategy.strategy)?.to_string(),
+                max_allocation: strategy.max_allocation,
+                liquidity_buffer: strategy.liquidity_buffer,
+            })
+        })
+        .transpose()
+}
+
+fn registered_strategy(deps: Deps) -> StdResult<(Strategy, String)> {
+    let config = read_config(deps.storage)?;
+    let native_denom = config
+        .native_denom
+        .ok_or_else(|| StdError::generic_err("native denom must be registered first"))?;
+    let strategy =
+        read_strategy(deps.storage)?.ok_or_else(|| StdError::generic_err("no strategy is set"))?;
+    Ok((strategy, native_denom))
+}
+
+/// Deposits reserves into the strategy, within its share of the reserves and leaving the
+/// liquidity buffer and the reserves held for filled redemptions in the converter.
+pub fn allocate_to_strategy(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    assert_role(deps.as_ref(), &info.sender, Role::Treasurer)?;
+    let (strategy, native_denom) = registered_strategy(deps.as_ref())?;
+
+    let balance = deps
+        .querier
+        .query_balance(env.contract.address.clone(), native_denom.clone())?
+        .amount;
+    let principal = read_strategy_principal(deps.storage)?;
+    let new_principal = principal.checked_add(amount)?;
+
+    let reserves = Uint256::from(balance.checked_add(principal)?);
+    let max_principal = reserves * strategy.max_allocation;
+    if Uint256::from(new_principal) > max_principal {
+        return Err(StdError::generic_err(format!(
+            "allocation exceeds the strategy cap of {}",
+            max_principal
+        )));
+    }
+    let kept = strategy
+        .liquidity_buffer
+        .checked_add(read_redemption_queue(deps.storage)?.reserved)?;
+    if balance < amount || balance - amount < kept {
+        return Err(StdError::generic_err(format!(
+            "allocation leaves less than {} in the converter",
+            kept
+        )));
+    }
+    store_strategy_principal(deps.storage, new_principal)?;
+    let strategy_addr = deps.api.addr_humanize(&strategy.strategy)?;
+    log_admin_action(
+        deps,
+        &env,
+        &info.sender,
+        "allocate_to_strategy",
+        &principal,
+        &new_principal,
+    )?;
+
+    Ok(Response::new()
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: strategy_addr.to_string(),
+            msg: to_json_binary(&StrategyExecuteMsg::Deposit {})?,
+            funds: vec![Coin {
+                denom: native_denom,
+                amount,
+            }],
+        }))
+        .add_attributes(vec![
+            ("action", "allocate_to_strategy"),
+            ("amount", &amount.to_string()),
+            ("principal", &new_principal.to_string()),
+        ]))
+}
+
+/// Withdraws allocated reserves from the strategy. Operators can pull reserves back as
+/// well, so instant redemptions can be restored without the treasurer.
+pub fn withdraw_from_strategy(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    if !has_role(deps.as_ref(), &info.sender, Role::Operator)? {
+        assert_role(deps.as_ref(), &info.sender, Role::Treasurer)?;
+    }
+    let (strategy, _) = registered_strategy(deps.as_ref())?;
+
+    let principal = read_strategy_principal(deps.storage)?;
+    if amount > principal {
+        return Err(StdError::generic_err(format!(
+            "cannot withdraw more than the allocated {}",
+            principal
+        )));
+    }
+    let new_principal = principal - amount;
+    store_strategy_principal(deps.storage, new_principal)?;
+    let strategy_addr = deps.api.addr_humanize(&strategy.strategy)?;
+    log_admin_action(
+        deps,
+        &env,
+        &info.sender,
+        "withdraw_from_strategy",
+        &principal,
+        &new_principal,
+    )?;
+
+    Ok(Response::new()
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: strategy_addr.to_string(),
+            msg: to_json_binary(&StrategyExecuteMsg::Withdraw { amount })?,
+            funds: vec![],
+        }))
+        .add_attributes(vec![
+            ("action", "withdraw_from_strategy"),
+            ("amount", &amount.to_string()),
+            ("principal", &new_principal.to_string()),
+        ]))
+}
+
+/// Withdraws the strategy value above the principal and sends what the strategy returns to
+/// the recipient, yield never becomes part of the basset backing.
+pub fn harvest_strategy_yield(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    assert_role(deps.as_ref(), &info.sender, Role::Treasurer)?;
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let (strategy, native_denom) = registered_strategy(deps.as_ref())?;
+
+    let strategy_addr = deps.api.addr_humanize(&strategy.strategy)?;
+    let total_value = query_strategy_value(deps.as_ref(), strategy_addr.clone())?;
+    let accrued_yield = total_value.saturating_sub(read_strategy_principal(deps.storage)?);
+    if accrued_yield.is_zero() {
+        return Err(StdError::generic_err("no strategy yield to harvest"));
+    }
+
+    // only what the strategy actually returns is paid out, never the backing reserves
+    let balance = deps
+        .querier
+        .query_balance(env.contract.address.clone(), native_denom.clone())?;
+    store_pending_harvest(
+        deps.storage,
+        &PendingHarvest {
+            recipient: recipient.clone(),
+            balance,
+            accrued_yield,
+        },
+    )?;
+    log_admin_action(
+        deps,
+        &env,
+        &info.sender,
+        "harvest_strategy_yield",
+        &DenomOrCw20::Native(native_denom),
+        &(accrued_yield, &recipient),
+    )?;
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(
+            WasmMsg::Execute {
+                contract_addr: strategy_addr.to_string(),
+                msg: to_json_binary(&StrategyExecuteMsg::Withdraw {
+                    amount: accrued_yield,
+                })?,
+                funds: vec![],
+            },
+            HARVEST_REPLY_ID,
+        ))
+        .add_attributes(vec![
+            ("action", "harvest_strategy_yield"),
+            ("recipient", recipient.as_str()),
+            ("yield", &accrued_yield.to_string()),
+        ]))
+}
+
+/// CW20 token receive handler.
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    let contract_addr = info.sender.clone();
+    match from_json(&cw20_msg.msg) {
+        Ok(Cw20HookMsg::ConvertBassetToNative {
+            ibc,
+            min_output,
+            deadline,
+        }) => {
+            // only basset beth token contract can execute this message
+            let conf = read_config(deps.storage)?;
+            if Some(deps.api.addr_canonicalize(contract_addr.as_str())?)
+                != conf.basset_token_address
+            {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+            let limits = ConversionLimits {
+                min_output,
+                deadline,
+            };
+            execute_convert_to_native(
+                deps,
+                env,
+                info,
+                cw20_msg.amount,
+                cw20_msg.sender,
+                ibc,
+                limits,
+            )
+        }
+        Ok(Cw20HookMsg::ConvertCw20ToBasset {
+            recipient,
+            min_output,
+            deadline,
+        }) => {
+            // only the cw20 underlying token contract can execute this message
+            let underlying = read_cw20_underlying(deps.storage)?;
+            if underlying != Some(deps.api.addr_canonicalize(contract_addr.as_str())?) {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+            let limits = ConversionLimits {
+                min_output,
+                deadline,
+            };
+            execute_convert_cw20_to_basset(
+                deps,
+                env,
+                cw20_msg.sender,
+                cw20_msg.amount,
+                recipient,
+                limits,
+            )
+        }
+        Ok(Cw20HookMsg::SwapBasset {
+            target_basset,
+            min_output,
+            deadline,
+        }) => {
+            let conf = read_config(deps.storage)?;
+            if Some(deps.api.addr_canonicalize(contract_addr.as_str())?)
+                != conf.basset_token_address
+            {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+            let limits = ConversionLimits {
+                min_output,
+                deadline,
+            };
+            swap_basset(
+                deps,
+                env,
+                cw20_msg.amount,
+                cw20_msg.sender,
+                target_basset,
+                limits,
+            )
+        }
+        Err(err) => Err(err),
+    }
+}
+
+pub fn register_tokens(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    basset_token_address: String,
+    native_denom: String,
+    denom_decimals: u8,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    assert_role(deps.as_ref(), &info.sender, Role::Admin)?;
+    if read_tokenfactory_basset(deps.storage)?.is_some() {
+        return Err(StdError::generic_err(
+            "the basset of this pair is already registered",
+        ));
+    }
+    let old_config = query_config(deps.as_ref())?;
+    let mut config = read_config(deps.storage)?;
+
+    // if the token contract is  already register we cannot change the address
+    if config.basset_token_address.is_none() {
+        config.basset_token_address = Some(deps.api.addr_canonicalize(&basset_token_address)?);
+    }
+
+    // if the token contract is  already register we cannot change the address
+    if config.native_denom.is_none() {
+        config.native_denom = Some(native_denom);
+    }
+
+    if config.denom_decimals.is_none() {
+        config.denom_decimals = Some(denom_decimals);
+    }
+
+    // decimals the conversion math cannot scale between are rejected up front
+    let basset_decimals = query_decimals(
+        deps.as_ref(),
+        deps.api
+            .addr_humanize(config.basset_token_address.as_ref().unwrap())?,
+    )?;
+    assert_supported_decimals(basset_decimals, config.denom_decimals.unwrap())?;
+
+    store_config(deps.storage).save(&config)?;
+    let new_config = query_config(deps.as_ref())?;
+    log_admin_action(
+        deps,
+        &env,
+        &info.sender,
+        "register_tokens",
+        &old_config,
+        &new_config,
+    )?;
+
+    Ok(Response::new().add_attributes(vec![("action", "register_token_contracts")]))
+}
+
+/// Registers a pair backed by a cw20 token. The token address takes the place of the native
+/// denom and its decimals are read from the token.
+pub fn register_cw20_tokens(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    basset_token_address: String,
+    underlying_token: String,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    assert_role(deps.as_ref(), &info.sender, Role::Admin)?;
+    if read_config(deps.storage)?.native_denom.is_some() {
+        return Err(StdError::generic_err(
+            "the underlying of this pair is already registered",
+        ));
+    }
+
+    let underlying_token = deps.api.addr_validate(&underlying_token)?;
+    let denom_decimals = query_decimals(deps.as_ref(), underlying_token.clone())?;
+    store_cw20_underlying(
+        deps.storage,
+        &deps.api.addr_canonicalize(underlying_token.as_str())?,
+    )?;
+    register_tokens(
+        deps,
+        env,
+        info,
+        basset_token_address,
+        underlying_token.to_string(),
+        denom_decimals,
+    )
+}
+
+/// Registers a pair whose basset is a tokenfactory denom created by the converter, which
+/// mints it on conversions and burns it on redemptions.
+pub fn register_tokenfactory_tokens(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    subdenom: String,
+    basset_decimals: u8,
+    native_denom: String,
+    denom_decimals: u8,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    assert_role(deps.as_ref(), &info.sender, Role::Admin)?;
+    let old_config = query_config(deps.as_ref())?;
+    let mut config = read_config(deps.storage)?;
+    if basset_registered(deps.as_ref(), &config)? {
+        return Err(StdError::generic_err(
+            "the basset of this pair is already registered",
+        ));
+    }
+
+    if config.native_denom.is_none() {
+        config.native_denom = Some(native_denom);
+    }
+    if config.denom_decimals.is_none() {
+        config.denom_decimals = Some(denom_decimals);
+    }
+    assert_supported_decimals(basset_decimals, config.denom_decimals.unwrap())?;
+
+    let denom = tokenfactory_denom(env.contract.address.as_str(), &subdenom);
+    store_tokenfactory_basset(
+        deps.storage,
+        &TokenFactoryBasset {
+            denom: denom.clone(),
+            decimals: basset_decimals,
+        },
+    )?;
+    store_config(deps.storage).save(&config)?;
+    let new_config = query_config(deps.as_ref())?;
+    log_admin_action(
+        deps,
+        &env,
+        &info.sender,
+        "register_tokens",
+        &old_config,
+        &new_config,
+    )?;
+
+    Ok(Response::new()
+        .add_message(TokenFactoryMsg::CreateDenom { subdenom })
+        .add_attributes(vec![
+            ("action", "register_tokenfactory_tokens"),
+            ("basset_denom", &denom),
+        ]))
+}
+
+/// Converts on behalf of a recipient, typically for an ibc-hooks memo where the sender is an
+/// intermediate address derived from the channel and the original sender that nobody controls.
+/// The conversion runs as a sub message to the converter itself, so that a failure pays the
+/// native funds to the recipient instead of bouncing the transfer back. Conversion limits are
+/// checked up front, a violation fails the transaction.
+pub fn convert_for_recipient(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    limits: ConversionLimits,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    let recipient = deps.api.addr_validate(&recipient)?;
+    // funds are never refunded to a restricted recipient, the transfer has to fail
+    assert_access_allowed(deps.as_ref(), info.sender.as_str(), recipient.as_str())?;
+    if info.funds.is_empty() {
+        return Err(StdError::generic_err("No funds are provided to convert"));
+    }
+    // the second leg runs in the same state, so it mints the amount checked here
+    limits.assert_deadline(&env)?;
+    if limits.min_output.is_some() {
+        refresh_exchange_rate(deps.branch(), &env)?;
+        let config = read_config(deps.storage)?;
+        let deposit_amount = native_deposit(deps.as_ref(), &config, &info)?;
+        limits.assert_min_output(basset_amount_for(
+            deps.as_ref(),
+            &config,
+            deposit_amount,
+            read_rounding_mode(deps.storage)?,
+        )?)?;
+    }
+
+    store_pending_conversion(
+        deps.storage,
+        &PendingConversion {
+            sender: info.sender.clone(),
+            recipient: recipient.clone(),
+            funds: info.funds.clone(),
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_always(
+            WasmMsg::Execute {
+                contract_addr: env.contract.address.to_string(),
+                msg: to_json_binary(&ExecuteMsg::ConvertNativeToBasset {
+                    recipient: Some(recipient.to_string()),
+                    min_output: limits.min_output,
+                    deadline: limits.deadline,
+                })?,
+                funds: info.funds,
+            },
+            CONVERT_FOR_RECIPIENT_REPLY_ID,
+        ))
+        .add_attributes(vec![
+            ("action", "convert_for_recipient"),
+            ("sender", info.sender.as_str()),
+            ("recipient", recipient.as_str()),
+        ]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> StdResult<Response<TokenFactoryMsg>> {
+    match msg.id {
+        CONVERT_FOR_RECIPIENT_REPLY_ID => convert_for_recipient_reply(deps, msg.result),
+        VERIFY_SUPPLY_REPLY_ID => verify_supply_reply(deps),
+        IBC_TRANSFER_REPLY_ID => ibc_transfer_reply(deps, msg.result),
+        HARVEST_REPLY_ID => harvest_reply(deps, env),
+        _ => Err(StdError::generic_err(format!(
+            "unknown reply id {}",
+            msg.id
+        ))),
+    }
+}
+
+/// Mints or burns basset in a sub message, the reply checks that the total supply moved by
+/// exactly the amount, so a token contract that does not mint or burn as told aborts.
+fn supply_change_msg(
+    deps: DepsMut,
+    basset: &DenomOrCw20,
+    msg: Cw20ExecuteMsg,
+) -> StdResult<Vec<SubMsg<TokenFactoryMsg>>> {
+    supply_change_msgs(deps, basset, vec![msg])
+}
+
+/// Sub messages for all the mints and burns of one execution, each reply checks the supply
+/// the ones before it leave. Tokenfactory coins are minted to the converter and sent on.
+fn supply_change_msgs(
+    deps: DepsMut,
+    basset: &DenomOrCw20,
+    msgs: Vec<Cw20ExecuteMsg>,
+) -> StdResult<Vec<SubMsg<TokenFactoryMsg>>> {
+    let basset = match basset {
+        DenomOrCw20::Native(denom) => DenomOrCw20::Native(denom.clone()),
+        DenomOrCw20::Cw20(contract_addr) => {
+            DenomOrCw20::Cw20(deps.api.addr_validate(contract_addr)?.to_string())
+        }
+    };
+    let mut supply = query_basset_supply(deps.as_ref(), &basset)?;
+    let mut expected_supplies: Vec<Uint128> = vec![];
+    let mut sub_msgs: Vec<SubMsg<TokenFactoryMsg>> = vec![];
+    for msg in msgs {
+        supply = match &msg {
+            Cw20ExecuteMsg::Mint { amount, .. } => supply.checked_add(*amount)?,
+            Cw20ExecuteMsg::Burn { amount } => supply.checked_sub(*amount)?,
+            _ => {
+                return Err(StdError::generic_err(
+                    "only mint and burn change the supply",
+                ))
+            }
+        };
+        expected_supplies.push(supply);
+        match (&basset, msg) {
+            (DenomOrCw20::Cw20(contract_addr), msg) => {
+                sub_msgs.push(SubMsg::reply_on_success(
+                    WasmMsg::Execute {
+                        contract_addr: contract_addr.clone(),
+                        msg: to_json_binary(&msg)?,
+                        funds: vec![],
+                    },
+                    VERIFY_SUPPLY_REPLY_ID,
+                ));
+            }
+            (DenomOrCw20::Native(denom), Cw20ExecuteMsg::Mint { recipient, amount }) => {
+                let amount = Coin {
+                    denom: denom.clone(),
+                    amount,
+                };
+                sub_msgs.push(SubMsg::reply_on_success(
+                    TokenFactoryMsg::MintTokens {
+                        amount: amount.clone(),
+                    },
+                    VERIFY_SUPPLY_REPLY_ID,
+                ));
+                sub_msgs.push(SubMsg::new(BankMsg::Send {
+                    to_address: recipient,
+                    amount: vec![amount],
+                }));
+            }
+            (DenomOrCw20::Native(denom), Cw20ExecuteMsg::Burn { amount }) => {
+                sub_msgs.push(SubMsg::reply_on_success(
+                    TokenFactoryMsg::BurnTokens {
+                        amount: Coin {
+                            denom: denom.clone(),
+                            amount,
+                        },
+                    },
+                    VERIFY_SUPPLY_REPLY_ID,
+                ));
+            }
+            (DenomOrCw20::Native(_), _) => unreachable!("only mint and burn get here"),
+        }
+    }
+    store_supply_check(
+        deps.storage,
+        &SupplyCheck {
+            basset,
+            expected_supplies,
+        },
+    )?;
+
+    Ok(sub_msgs)
+}
+
+fn verify_supply_reply(deps: DepsMut) -> StdResult<Response<TokenFactoryMsg>> {
+    let mut check = read_supply_check(deps.storage)?
+        .filter(|check| !check.expected_supplies.is_empty())
+        .ok_or_else(|| StdError::generic_err("no supply change is pending"))?;
+    let expected_supply = check.expected_supplies.remove(0);
+    let supply = query_basset_supply(deps.as_ref(), &check.basset)?;
+    if supply != expected_supply {
+        return Err(StdError::generic_err(format!(
+            "basset supply is {} but {} was expected",
+            supply, expected_supply
+        )));
+    }
+
+    if check.expected_supplies.is_empty() {
+        remove_supply_check(deps.storage);
+    } else {
+        store_supply_check(deps.storage, &check)?;
+    }
+    Ok(Response::new())
+}
+
+/// Pays out the yield the strategy returned, at most the yield the harvest asked for.
+fn harvest_reply(deps: DepsMut, env: Env) -> StdResult<Response<TokenFactoryMsg>> {
+    let harvest = read_pending_harvest(deps.storage)?;
+    remove_pending_harvest(deps.storage);
+
+    let balance = deps
+        .querier
+        .query_balance(env.contract.address, harvest.balance.denom.clone())?;
+    let returned = balance
+        .amount
+        .saturating_sub(harvest.balance.amount)
+        .min(harvest.accrued_yield);
+    if returned.is_zero() {
+        return Err(StdError::generic_err("strategy returned no yield"));
+    }
+
+    Ok(Response::new()
+        .add_message(CosmosMsg::Bank(BankMsg::Send {
+            to_address: harvest.recipient.to_string(),
+            amount: vec![Coin {
+                denom: balance.denom,
+                amount: returned,
+            }],
+        }))
+        .add_attributes(vec![("harvested", returned.to_string())]))
+}
+
+/// Keys the escrow of an ibc redemption by the packet sequence of its transfer.
+fn ibc_transfer_reply(deps: DepsMut, result: SubMsgResult) -> StdResult<Response<TokenFactoryMsg>> {
+    let redemption = read_pending_ibc_redemption(deps.storage)?;
+    remove_pending_ibc_redemption(deps.storage);
+    let response = result.into_result().map_err(StdError::generic_err)?;
+    let sequence = transfer_sequence(response.data.as_ref())?;
+    store_ibc_redemption(deps.storage, sequence, &redemption)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("channel_id", redemption.channel_id),
+        ("sequence", sequence.to_string()),
+    ]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, _env: Env, msg: SudoMsg) -> StdResult<Response<TokenFactoryMsg>> {
+    match msg {
+        SudoMsg::IbcLifecycleComplete(IbcLifecycleComplete::IbcAck {
+            channel,
+            sequence,
+            success,
+            ..
+        }) => complete_ibc_redemption(deps, channel, sequence, success),
+        SudoMsg::IbcLifecycleComplete(IbcLifecycleComplete::IbcTimeout { channel, sequence }) => {
+            complete_ibc_redemption(deps, channel, sequence, false)
+        }
+    }
+}
+
+/// Releases the escrow of an ibc redemption. A failed or timed out transfer returned the
+/// native amount to the converter, it is credited to the owner of the redemption.
+fn complete_ibc_redemption(
+    deps: DepsMut,
+    channel: String,
+    sequence: u64,
+    success: bool,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    let redemption = read_ibc_redemption(deps.storage, &channel, sequence)?.ok_or_else(|| {
+        StdError::generic_err(format!(
+            "no ibc redemption is pending for sequence {} on {}",
+            sequence, channel
+        ))
+    })?;
+    remove_ibc_redemption(deps.storage, &channel, sequence);
+
+    let res = Response::new().add_attributes(vec![
+        ("channel_id", channel),
+        ("sequence", sequence.to_string()),
+    ]);
+    if success {
+        return Ok(res.add_attribute("action", "complete_ibc_redemption"));
+    }
+    Ok(res
+        .add_message(CosmosMsg::Bank(BankMsg::Send {
+            to_address: redemption.owner.to_string(),
+            amount: vec![redemption.amount.clone()],
+        }))
+        .add_attributes(vec![
+            ("action", "refund_ibc_redemption".to_string()),
+            ("recipient", redemption.owner.to_string()),
+            ("amount", redemption.amount.to_string()),
+        ]))
+}
+
+fn convert_for_recipient_reply(
+    deps: DepsMut,
+    result: SubMsgResult,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    let pending = read_pending_conversion(deps.storage)?;
+    remove_pending_conversion(deps.storage);
+
+    match result {
+        SubMsgResult::Ok(_) => Ok(Response::default()),
+        SubMsgResult::Err(err) => Ok(Response::new()
+            .add_message(CosmosMsg::Bank(BankMsg::Send {
+                to_address: pending.recipient.to_string(),
+                amount: pending.funds,
+            }))
+            .add_attributes(vec![
+                ("action", "refund_failed_conversion"),
+                ("recipient", pending.recipient.as_str()),
+                ("error", &err),
+            ])),
+    }
+}
+
+pub(crate) fn execute_convert_to_basset(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: Option<String>,
+    limits: ConversionLimits,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => info.sender.clone(),
+    };
+    limits.assert_deadline(&env)?;
+    assert_not_paused(deps.as_ref())?;
+    assert_can_mint(deps.as_ref())?;
+    // the second leg of a conversion for a recipient was checked against the actual sender
+    if info.sender != env.contract.address {
+        assert_access_allowed(deps.as_ref(), info.sender.as_str(), recipient.as_str())?;
+    }
+    // the funds of a conversion for a recipient are the recipient's, and the conversion is
+    // reported with the sender of its first leg
+    let (sender, refund_to) = if info.sender == env.contract.address {
+        (
+            read_pending_conversion(deps.storage)?.sender,
+            recipient.clone(),
+        )
+    } else {
+        (info.sender.clone(), info.sender.clone())
+    };
+    convert_deposit(deps, env, info, sender, recipient, refund_to, limits)
+}
+
+/// Mints for the native funds of a conversion that passed its checks. A refused conversion
+/// returns the funds to `refund_to`.
+fn convert_deposit(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: Addr,
+    recipient: Addr,
+    refund_to: Addr,
+    limits: ConversionLimits,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    let config = read_config(deps.storage)?;
+    refresh_exchange_rate(deps.branch(), &env)?;
+
+    let deposit_amount = native_deposit(deps.as_ref(), &config, &info)?;
+    mint_for_deposit(
+        deps,
+        &env,
+        &sender,
+        recipient,
+        deposit_amount,
+        limits,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: refund_to.to_string(),
+            amount: info.funds,
+        }),
+    )
+}
+
+/// Converts a cw20 underlying received through the send hook.
+pub(crate) fn execute_convert_cw20_to_basset(
+    mut deps: DepsMut,
+    env: Env,
+    sender: String,
+    amount: Uint128,
+    recipient: Option<String>,
+    limits: ConversionLimits,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    let config = read_config(deps.storage)?;
+    let sender = deps.api.addr_validate(&sender)?;
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => sender.clone(),
+    };
+    limits.assert_deadline(&env)?;
+    assert_not_paused(deps.as_ref())?;
+    assert_can_mint(deps.as_ref())?;
+    assert_access_allowed(deps.as_ref(), sender.as_str(), recipient.as_str())?;
+    refresh_exchange_rate(deps.branch(), &env)?;
+
+    let refund = transfer_msg(
+        &reserve_asset(deps.as_ref(), &config)?,
+        sender.as_str(),
+        amount,
+    )?;
+    mint_for_deposit(deps, &env, &sender, recipient, amount, limits, refund)
+}
+
+/// Mints basset for a deposit of the underlying, the refund returns the deposit if the
+/// backing would no longer cover the supply.
+fn mint_for_deposit(
+    mut deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    recipient: Addr,
+    deposit_amount: Uint128,
+    limits: ConversionLimits,
+    refund: CosmosMsg<TokenFactoryMsg>,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    let config = read_config(deps.storage)?;
+    let mint_amount = basset_amount_for(
+        deps.as_ref(),
+        &config,
+        deposit_amount,
+        read_rounding_mode(deps.storage)?,
+    )?;
+    limits.assert_min_output(mint_amount)?;
+    let backing = read_backing(deps.storage)?.checked_add(deposit_amount)?;
+    let deficit = mint_rounding_deficit(deps.as_ref(), &config, deposit_amount, mint_amount)?;
+    if let Some(event) = backing_violation(
+        deps.as_ref(),
+        &config,
+        backing,
+        mint_amount,
+        Uint128::zero(),
+        deficit,
+    )? {
+        return trip_invariant(deps, env, event, Some(refund));
+    }
+    store_backing(deps.storage, backing)?;
+    record_rounding_deficit(deps.storage, deficit)?;
+    let event = convert_event(
+        DIRECTION_TO_BASSET,
+        &pair_name(deps.as_ref(), &config)?,
+        sender.as_str(),
+        recipient.as_str(),
+        deposit_amount,
+        mint_amount,
+        conversion_dust(deps.as_ref(), &config, true, deposit_amount, mint_amount)?,
+    );
+
+    let basset = basset_asset(deps.as_ref(), &config)?;
+    let mint_msg = supply_change_msg(
+        deps.branch(),
+        &basset,
+        Cw20ExecuteMsg::Mint {
+            recipient: recipient.to_string(),
+            amount: mint_amount,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_submessages(mint_msg)
+        .add_attributes(vec![
+            ("action", "convert-to-basset"),
+            ("recipient", recipient.as_str()),
+            ("minted_amount", &mint_amount.to_string()),
+            (
+                "rounding_mode",
+                &read_rounding_mode(deps.storage)?.to_string(),
+            ),
+        ]))
+}
+
+/// Converts one native deposit for many recipients, e.g. to migrate holders of an older token.
+/// Every recipient gets its own mint and the whole batch fails if any of them fails.
+pub fn batch_convert_to_basset(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipients: Vec<(String, Uint128)>,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    assert_role(deps.as_ref(), &info.sender, Role::Operator)?;
+    if recipients.is_empty() || recipients.len() > MAX_BATCH_RECIPIENTS {
+        return Err(StdError::generic_err(format!(
+            "a batch converts for 1 to {} recipients",
+            MAX_BATCH_RECIPIENTS
+        )));
+    }
+    let config = read_config(deps.storage)?;
+    assert_not_paused(deps.as_ref())?;
+    assert_can_mint(deps.as_ref())?;
+    refresh_exchange_rate(deps.branch(), &env)?;
+
+    let (deposit_amount, _) = compute_mint_amount(deps.as_ref(), &config, &info)?;
+    let total = recipients
+        .iter()
+        .try_fold(Uint128::zero(), |total, (_, amount)| {
+            total.checked_add(*amount)
+        })?;
+    if total != deposit_amount {
+        return Err(StdError::generic_err(format!(
+            "recipient amounts add up to {} but {} was sent",
+            total, deposit_amount
+        )));
+    }
+
+    let rounding = read_rounding_mode(deps.storage)?;
+    let pair = pair_name(deps.as_ref(), &config)?;
+    let mut mints: Vec<(Addr, Uint128)> = vec![];
+    let mut events: Vec<Event> = vec![];
+    let mut total_minted = Uint128::zero();
+    for (recipient, amount) in recipients.iter() {
+        let recipient = deps.api.addr_validate(recipient)?;
+        assert_access_allowed(deps.as_ref(), info.sender.as_str(), recipient.as_str())?;
+        let mint_amount = basset_amount_for(deps.as_ref(), &config, *amount, rounding)?;
+        if mint_amount.is_zero() {
+            return Err(StdError::generic_err(format!(
+                "the amount for {} converts to no basset",
+                recipient
+            )));
+        }
+        total_minted = total_minted.checked_add(mint_amount)?;
+        events.push(convert_event(
+            DIRECTION_TO_BASSET,
+            &pair,
+            info.sender.as_str(),
+            recipient.as_str(),
+            *amount,
+            mint_amount,
+            conversion_dust(deps.as_ref(), &config, true, *amount, mint_amount)?,
+        ));
+        mints.push((recipient, mint_amount));
+    }
+
+    let backing = read_backing(deps.storage)?.checked_add(deposit_amount)?;
+    let deficit = mint_rounding_deficit(deps.as_ref(), &config, deposit_amount, total_minted)?;
+    if let Some(event) = backing_violation(
+        deps.as_ref(),
+        &config,
+        backing,
+        total_minted,
+        Uint128::zero(),
+        deficit,
+    )? {
+        return trip_invariant(
+            deps,
+            &env,
+            event,
+            Some(CosmosMsg::Bank(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: info.funds,
+            })),
+        );
+    }
+    store_backing(deps.storage, backing)?;
+    record_rounding_deficit(deps.storage, deficit)?;
+
+    let basset = basset_asset(deps.as_ref(), &config)?;
+    let mint_msgs = supply_change_msgs(
+        deps.branch(),
+        &basset,
+        mints
+            .into_iter()
+            .map(|(recipient, amount)| Cw20ExecuteMsg::Mint {
+                recipient: recipient.to_string(),
+                amount,
+            })
+            .collect(),
+    )?;
+
+    Ok(Response::new()
+        .add_events(events)
+        .add_submessages(mint_msgs)
+        .add_attributes(vec![
+            ("action", "batch_convert_to_basset".to_string()),
+            ("recipients", recipients.len().to_string()),
+            ("minted_amount", total_minted.to_string()),
+        ]))
+}
+
+/// Mints the basset to the converter and sends it to the custody contract, so the sender
+/// ends up with collateral instead of basset.
+pub fn convert_and_deposit_collateral(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    custody: String,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    let config = read_config(deps.storage)?;
+    if read_tokenfactory_basset(deps.storage)?.is_some() {
+        return Err(StdError::generic_err(
+            "collateral deposits need a cw20 basset",
+        ));
+    }
+    assert_not_paused(deps.as_ref())?;
+    assert_can_mint(deps.as_ref())?;
+    assert_access_allowed(deps.as_ref(), info.sender.as_str(), info.sender.as_str())?;
+
+    let custody_raw = deps.api.addr_canonicalize(&custody)?;
+    if read_custody(deps.storage)? != Some(custody_raw) {
+        return Err(StdError::generic_err(
+            "custody contract does not match the registered custody",
+        ));
+    }
+    refresh_exchange_rate(deps.branch(), &env)?;
+
+    let (deposit_amount, mint_amount) = compute_mint_amount(deps.as_ref(), &config, &info)?;
+    let backing = read_backing(deps.storage)?.checked_add(deposit_amount)?;
+    let deficit = mint_rounding_deficit(deps.as_ref(), &config, deposit_amount, mint_amount)?;
+    if let Some(event) = backing_violation(
+        deps.as_ref(),
+        &config,
+        backing,
+        mint_amount,
+        Uint128::zero(),
+        deficit,
+    )? {
+        return trip_invariant(
+            deps,
+            &env,
+            event,
+            Some(CosmosMsg::Bank(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: info.funds,
+            })),
+        );
+    }
+    store_backing(deps.storage, backing)?;
+    record_rounding_deficit(deps.storage, deficit)?;
+    let event = convert_event(
+        DIRECTION_TO_BASSET,
+        &pair_name(deps.as_ref(), &config)?,
+        info.sender.as_str(),
+        &custody,
+        deposit_amount,
+        mint_amount,
+        conversion_dust(deps.as_ref(), &config, true, deposit_amount, mint_amount)?,
+    );
+    let basset_token_address = deps
+        .api
+        .addr_humanize(&config.basset_token_address.unwrap())?
+        .to_string();
+
+    let mint_msg = supply_change_msg(
+        deps.branch(),
+        &DenomOrCw20::Cw20(basset_token_address.clone()),
+        Cw20ExecuteMsg::Mint {
+            recipient: env.contract.address.to_string(),
+            amount: mint_amount,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_submessages(mint_msg)
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: basset_token_address,
+            msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                contract: custody.clone(),
+                amount: mint_amount,
+                msg: to_json_binary(&CustodyCw20HookMsg::DepositCollateral {
+                    borrower: Some(info.sender.to_string()),
+                })?,
+            })?,
+            funds: vec![],
+        }))
+        .add_attributes(vec![
+            ("action", "convert_and_deposit_collateral"),
+            ("borrower", info.sender.as_str()),
+            ("custody", &custody),
+            ("minted_amount", &mint_amount.to_string()),
+            (
+                "rounding_mode",
+                &read_rounding_mode(deps.storage)?.to_string(),
+            ),
+        ]))
+}
+
+/// Bounds the sender signed a conversion with.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ConversionLimits {
+    pub min_output: Option<Uint128>,
+    pub deadline: Option<Timestamp>,
+}
+
+impl ConversionLimits {
+    fn assert_deadline(&self, env: &Env) -> StdResult<()> {
+        match self.deadline {
+            Some(deadline) if env.block.time > deadline => {
+                Err(StdError::generic_err("conversion deadline has passed"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn assert_min_output(&self, output: Uint128) -> StdResult<()> {
+        match self.min_output {
+            Some(min_output) if output < min_output => Err(StdError::generic_err(format!(
+                "output {} is below the minimum output {}",
+                output, min_output
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Typed conversion event, the converter charges no fee.
+fn convert_event(
+    direction: &str,
+    pair: &str,
+    sender: &str,
+    recipient: &str,
+    input_amount: Uint128,
+    output_amount: Uint128,
+    dust: Uint128,
+) -> Event {
+    Event::new(CONVERT_EVENT).add_attributes(vec![
+        (CONVERT_DIRECTION_KEY, direction.to_string()),
+        (CONVERT_PAIR_KEY, pair.to_string()),
+        (CONVERT_SENDER_KEY, sender.to_string()),
+        (CONVERT_RECIPIENT_KEY, recipient.to_string()),
+        (CONVERT_INPUT_AMOUNT_KEY, input_amount.to_string()),
+        (CONVERT_OUTPUT_AMOUNT_KEY, output_amount.to_string()),
+        (CONVERT_FEE_KEY, Uint128::zero().to_string()),
+        (CONVERT_DUST_KEY, dust.to_string()),
+    ])
+}
+
+fn pair_name(deps: Deps, config: &Config) -> StdResult<String> {
+    let basset = match basset_asset(deps, config)? {
+        DenomOrCw20::Native(denom) => denom,
+        DenomOrCw20::Cw20(contract_addr) => contract_addr,
+    };
+    Ok(format!(
+        "{}:{}",
+        config.native_denom.as_ref().unwrap(),
+        basset
+    ))
+}
+
+/// Input left over by rounding, the input minus what the output costs rounded up.
+fn conversion_dust(
+    deps: Deps,
+    config: &Config,
+    to_basset: bool,
+    input_amount: Uint128,
+    output_amount: Uint128,
+) -> StdResult<Uint128> {
+    let spent = if to_basset {
+        native_amount_for(deps, config, output_amount, RoundingMode::Ceil)?
+    } else {
+        basset_amount_for(deps, config, output_amount, RoundingMode::Ceil)?
+    };
+    Ok(input_amount.saturating_sub(spent))
+}
+
+/// Native value a mint gave beyond the deposit. Only rounding up gives any, the deposit
+/// backs a mint rounded down.
+fn mint_rounding_deficit(
+    deps: Deps,
+    config: &Config,
+    deposit_amount: Uint128,
+    minted: Uint128,
+) -> StdResult<Uint128> {
+    Ok(native_amount_for(deps, config, minted, RoundingMode::Ceil)?.saturating_sub(deposit_amount))
+}
+
+/// Native value a redemption returned beyond the burned basset rounded down.
+fn redeem_rounding_deficit(
+    deps: Deps,
+    config: &Config,
+    burned: Uint128,
+    returned: Uint128,
+) -> StdResult<Uint128> {
+    // an amount that rounds down to nothing was returned entirely by rounding up
+    let floor = native_amount_for(deps, config, burned, RoundingMode::Floor).unwrap_or_default();
+    Ok(returned.saturating_sub(floor))
+}
+
+fn record_rounding_deficit(storage: &mut dyn Storage, deficit: Uint128) -> StdResult<()> {
+    if deficit.is_zero() {
+        return Ok(());
+    }
+    let total = read_rounding_deficit(storage)?.checked_add(deficit)?;
+    store_rounding_deficit(storage, total)
+}
+
+/// Native amount sent with the message and the basset amount to mint for it.
+fn compute_mint_amount(
+    deps: Deps,
+    config: &Config,
+    info: &MessageInfo,
+) -> StdResult<(Uint128, Uint128)> {
+    let deposit_amount = native_deposit(deps, config, info)?;
+    let rounding = read_rounding_mode(deps.storage)?;
+    Ok((
+        deposit_amount,
+        basset_amount_for(deps, config, deposit_amount, rounding)?,
+    ))
+}
+
+/// Amount of the registered native denom sent with the message.
+fn native_deposit(deps: Deps, config: &Config, info: &MessageInfo) -> StdResult<Uint128> {
+    if !basset_registered(deps, config)? || config.native_denom.is_none() {
+        return Err(StdError::generic_err(
+            "native denom must be registered first",
+        ));
+    }
+    if read_cw20_underlying(deps.storage)?.is_some() {
+        return Err(StdError::generic_err(
+            "the underlying is a cw20 token, send it to the converter to convert",
+        ));
+    }
+    let coin_denom = config.native_denom.as_ref().unwrap();
+
+    if info.funds.len() != 1 {
+        return Err(StdError::generic_err(
+            "The execute_convert_to_basset function only receives one registered native denom.",
+        ));
+    }
+
+    let coin = info
+        .funds
+        .iter()
+        .find(|x| &x.denom == coin_denom && x.amount > Uint128::zero())
+        .ok_or_else(|| {
+            StdError::generic_err(format!("No {} assets are provided to deposit", coin_denom))
+        })?;
+    Ok(coin.amount)
+}
+
+/// Basset minted for a native amount, for a registered pair.
+fn basset_amount_for(
+    deps: Deps,
+    config: &Config,
+    amount: Uint128,
+    rounding: RoundingMode,
+) -> StdResult<Uint128> {
+    let (_, basset_decimals) = basset_supply(deps, config)?;
+
+    // should convert to basset decimals
+    let amount = convert_to_basset_decimals(
+        amount,
+        basset_decimals,
+        config.denom_decimals.unwrap(),
+        rounding,
+    )?;
+    apply_exchange_rate(amount, read_exchange_rate(deps.storage)?, rounding)
+}
+
+/// Native amount returned for a basset amount, for a registered pair.
+fn native_amount_for(
+    deps: Deps,
+    config: &Config,
+    amount: Uint128,
+    rounding: RoundingMode,
+) -> StdResult<Uint128> {
+    let (_, basset_decimals) = basset_supply(deps, config)?;
+
+    // should convert to native decimals
+    let native_amount = remove_exchange_rate(amount, read_exchange_rate(deps.storage)?, rounding)?;
+    convert_to_denom_decimals(
+        native_amount,
+        basset_decimals,
+        config.denom_decimals.unwrap(),
+        rounding,
+    )
+}
+
+/// Redeems tokenfactory basset sent as funds, the counterpart of the cw20 receive hook.
+pub fn convert_basset_coins_to_native(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    ibc: Option<IbcTransferInfo>,
+    limits: ConversionLimits,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    let basset = read_tokenfactory_basset(deps.storage)?.ok_or_else(|| {
+        StdError::generic_err("the basset of this pair is not a tokenfactory denom")
+    })?;
+    let amount = match info.funds.as_slice() {
+        [coin] if coin.denom == basset.denom && !coin.amount.is_zero() => coin.amount,
+        _ => {
+            return Err(StdError::generic_err(format!(
+                "send exactly one coin of {} to convert",
+                basset.denom
+            )))
+        }
+    };
+    let sender = info.sender.to_string();
+    execute_convert_to_native(deps, env, info, amount, sender, ibc, limits)
+}
+
+pub(crate) fn execute_convert_to_native(
+    mut deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    amount: Uint128,
+    sender: String,
+    ibc: Option<IbcTransferInfo>,
+    limits: ConversionLimits,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    let config = read_config(deps.storage)?;
+    if !basset_registered(deps.as_ref(), &config)? || config.native_denom.is_none() {
+        return Err(StdError::generic_err(
+            "native or basset token must be registered first",
+        ));
+    }
+    limits.assert_deadline(&env)?;
+    assert_not_paused(deps.as_ref())?;
+    assert_can_redeem(deps.as_ref())?;
+    assert_access_allowed(deps.as_ref(), &sender, &sender)?;
+    refresh_exchange_rate(deps.branch(), &env)?;
+
+    let return_amount = native_amount_for(
+        deps.as_ref(),
+        &config,
+        amount,
+        read_rounding_mode(deps.storage)?,
+    )?;
+    limits.assert_min_output(return_amount)?;
+    let reserve = reserve_asset(deps.as_ref(), &config)?;
+    let pair = pair_name(deps.as_ref(), &config)?;
+    let dust = conversion_dust(deps.as_ref(), &config, false, amount, return_amount)?;
+
+    // earlier tickets are served first, and reserves held for filled tickets are not spent
+    let (queue, available) = fill_redemption_queue(deps.branch(), &env, &reserve)?;
+    if queue.head < queue.next_id || available < return_amount {
+        if ibc.is_some() {
+            return Err(StdError::generic_err(
+                "not enough reserves to redeem over ibc",
+            ));
+        }
+        // the conversion event is emitted once the ticket is claimed
+        let deficit = redeem_rounding_deficit(deps.as_ref(), &config, amount, return_amount)?;
+        return queue_redemption(deps, queue, sender, amount, return_amount, dust, deficit);
+    }
+
+    let backing = read_backing(deps.storage)?.saturating_sub(return_amount);
+    let deficit = redeem_rounding_deficit(deps.as_ref(), &config, amount, return_amount)?;
+    if let Some(event) = backing_violation(
+        deps.as_ref(),
+        &config,
+        backing,
+        Uint128::zero(),
+        amount,
+        deficit,
+    )? {
+        let refund = transfer_msg(&basset_asset(deps.as_ref(), &config)?, &sender, amount)?;
+        return trip_invariant(deps, &env, event, Some(refund));
+    }
+    store_backing(deps.storage, backing)?;
+    record_rounding_deficit(deps.storage, deficit)?;
+
+    // the same amount leaves the converter, either to the sender or over ibc
+    let (return_msg, recipient) = match (ibc, &reserve) {
+        (Some(_), DenomOrCw20::Cw20(_)) => {
+            return Err(StdError::generic_err(
+                "ibc redemptions need a native underlying",
+            ))
+        }
+        (Some(ibc), DenomOrCw20::Native(denom)) => {
+            if ibc.timeout_seconds == 0 {
+                return Err(StdError::generic_err(
+                    "ibc timeout must be greater than zero",
+                ));
+            }
+            if ibc.receiver.is_empty() {
+                return Err(StdError::generic_err("ibc receiver must not be empty"));
+            }
+            // the native amount is escrowed for the sender until the transfer is acknowledged
+            let amount = Coin {
+                denom: denom.clone(),
+                amount: return_amount,
+            };
+            store_pending_ibc_redemption(
+                deps.storage,
+                &IbcRedemption {
+                    owner: deps.api.addr_validate(&sender)?,
+                    channel_id: ibc.channel_id.clone(),
+                    amount: amount.clone(),
+                },
+            )?;
+            (
+                SubMsg::reply_on_success(
+                    transfer_with_callback(
+                        &ibc.channel_id,
+                        env.contract.address.as_str(),
+                        &ibc.receiver,
+                        &amount,
+                        env.block.time.plus_seconds(ibc.timeout_seconds),
+                        env.contract.address.as_str(),
+                    ),
+                    IBC_TRANSFER_REPLY_ID,
+                ),
+                ibc.receiver,
+            )
+        }
+        (None, _) => (
+            SubMsg::new(transfer_msg(&reserve, &sender, return_amount)?),
+            sender.clone(),
+        ),
+    };
+    let event = convert_event(
+        DIRECTION_TO_NATIVE,
+        &pair,
+        &sender,
+        &recipient,
+        amount,
+        return_amount,
+        dust,
+    );
+
+    let basset = basset_asset(deps.as_ref(), &config)?;
+    let burn_msg = supply_change_msg(deps.branch(), &basset, Cw20ExecuteMsg::Burn { amount })?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_submessage(return_msg)
+        .add_submessages(burn_msg)
+        .add_attributes(vec![
+            ("action", "convert-to-native"),
+            ("recipient", &recipient),
+            ("return_amount", &return_amount.to_string()),
+            ("burn_amount", &amount.to_string()),
+            (
+                "rounding_mode",
+                &read_rounding_mode(deps.storage)?.to_string(),
+            ),
+        ]))
+}
+
+/// Registers the converter of an equivalent pair. Swaps move the reserves to that converter,
+/// so it has to hold the same native denom.
+pub fn set_equivalent_pair(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    basset_token: String,
+    converter: Option<String>,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    assert_role(deps.as_ref(), &info.sender, Role::Admin)?;
+    let basset_token = deps.api.addr_validate(&basset_token)?;
+    let basset_raw = deps.api.addr_canonicalize(basset_token.as_str())?;
+    let old_converter = match read_equivalent_pair(deps.storage, &basset_raw)? {
+        Some(converter) => Some(deps.api.addr_humanize(&converter)?.to_string()),
+        None => None,
+    };
+
+    match &converter {
+        Some(converter) => {
+            let config = read_config(deps.storage)?;
+            if read_tokenfactory_basset(deps.storage)?.is_some() {
+                return Err(StdError::generic_err("swaps need a cw20 basset"));
+            }
+            if config.basset_token_address.is_none() || config.native_denom.is_none() {
+                return Err(StdError::generic_err(
+                    "native or basset token must be registered first",
+                ));
+            }
+            if read_cw20_underlying(deps.storage)?.is_some() {
+                return Err(StdError::generic_err("swaps need a native underlying"));
+            }
+            if config.basset_token_address.as_ref() == Some(&basset_raw) {
+                return Err(StdError::generic_err(
+                    "the basset of this pair cannot be its own equivalent",
+                ));
+            }
+            let converter = deps.api.addr_validate(converter)?;
+            let peer_config = query_converter_config(deps.as_ref(), converter.clone())?;
+            if peer_config.basset_token_address.as_deref() != Some(basset_token.as_str()) {
+                return Err(StdError::generic_err(format!(
+                    "{} is not the basset of converter {}",
+                    basset_token, converter
+                )));
+            }
+            if peer_config.native_denom != config.native_denom {
+                return Err(StdError::generic_err(
+                    "equivalent pairs must hold the same native denom",
+                ));
+            }
+            store_equivalent_pair(
+                deps.storage,
+                &basset_raw,
+                &deps.api.addr_canonicalize(converter.as_str())?,
+            )?;
+        }
+        None => remove_equivalent_pair(deps.storage, &basset_raw),
+    }
+    log_admin_action(
+        deps,
+        &env,
+        &info.sender,
+        "set_equivalent_pair",
+        &old_converter,
+        &converter,
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_equivalent_pair"),
+        ("basset_token", basset_token.as_str()),
+        ("converter", converter.as_deref().unwrap_or("none")),
+    ]))
+}
+
+/// Burns basset and moves its reserves to the converter of an equivalent pair, which mints
+/// its basset to the sender in the same transaction. Swaps are not queued, the reserves of
+/// this pair have to cover them.
+pub fn swap_basset(
+    mut deps: DepsMut,
+    env: Env,
+    amount: Uint128,
+    sender: String,
+    target_basset: String,
+    limits: ConversionLimits,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    let config = read_config(deps.storage)?;
+    limits.assert_deadline(&env)?;
+    assert_not_paused(deps.as_ref())?;
+    assert_can_redeem(deps.as_ref())?;
+    assert_access_allowed(deps.as_ref(), &sender, &sender)?;
+    let converter =
+        match read_equivalent_pair(deps.storage, &deps.api.addr_canonicalize(&target_basset)?)? {
+            Some(converter) => deps.api.addr_humanize(&converter)?,
+            None => {
+                return Err(StdError::generic_err(format!(
+                    "{} is not registered as an equivalent basset",
+                    target_basset
+                )))
+            }
+        };
+    refresh_exchange_rate(deps.branch(), &env)?;
+
+    let native_amount = native_amount_for(
+        deps.as_ref(),
+        &config,
+        amount,
+        read_rounding_mode(deps.storage)?,
+    )?;
+    if native_amount.is_zero() {
+        return Err(StdError::generic_err(
+            "cannot swap; the amount is worth no native token",
+        ));
+    }
+    let native_denom = config.native_denom.clone().unwrap();
+    let (queue, available) = fill_redemption_queue(
+        deps.branch(),
+        &env,
+        &DenomOrCw20::Native(native_denom.clone()),
+    )?;
+    if queue.head < queue.next_id || available < native_amount {
+        return Err(StdError::generic_err("not enough reserves to swap"));
+    }
+
+    let basset_token_address = deps
+        .api
+        .addr_humanize(config.basset_token_address.as_ref().unwrap())?
+        .to_string();
+    let backing = read_backing(deps.storage)?.saturating_sub(native_amount);
+    let deficit = redeem_rounding_deficit(deps.as_ref(), &config, amount, native_amount)?;
+    if let Some(event) = backing_violation(
+        deps.as_ref(),
+        &config,
+        backing,
+        Uint128::zero(),
+        amount,
+        deficit,
+    )? {
+        return trip_invariant(
+            deps,
+            &env,
+            event,
+            Some(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: basset_token_address,
+                msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: sender,
+                    amount,
+                })?,
+                funds: vec![],
+            })),
+        );
+    }
+    store_backing(deps.storage, backing)?;
+    record_rounding_deficit(deps.storage, deficit)?;
+
+    let event = convert_event(
+        DIRECTION_TO_NATIVE,
+        &pair_name(deps.as_ref(), &config)?,
+        &sender,
+        converter.as_str(),
+        amount,
+        native_amount,
+        conversion_dust(deps.as_ref(), &config, false, amount, native_amount)?,
+    );
+    let burn_msg = supply_change_msg(
+        deps.branch(),
+        &DenomOrCw20::Cw20(basset_token_address),
+        Cw20ExecuteMsg::Burn { amount },
+    )?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_submessages(burn_msg)
+        .add_message(WasmMsg::Execute {
+            contract_addr: converter.to_string(),
+            msg: to_json_binary(&ExecuteMsg::MintSwappedBasset {
+                recipient: sender.clone(),
+                min_output: limits.min_output,
+                deadline: limits.deadline,
+            })?,
+            funds: vec![Coin {
+                denom: native_denom,
+                amount: native_amount,
+            }],
+        })
+        .add_attributes(vec![
+            ("action", "swap_basset"),
+            ("sender", &sender),
+            ("target_basset", &target_basset),
+            ("burn_amount", &amount.to_string()),
+            ("native_amount", &native_amount.to_string()),
+        ]))
+}
+
+/// Second leg of a swap, the reserves come from the equivalent converter and the
+/// target basset goes to the swapping address.
+pub fn mint_swapped_basset(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    limits: ConversionLimits,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if !is_equivalent_converter(deps.storage, &sender_raw)? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+    let recipient = deps.api.addr_validate(&recipient)?;
+    limits.assert_deadline(&env)?;
+    assert_not_paused(deps.as_ref())?;
+    assert_can_mint(deps.as_ref())?;
+    // the swapping user provided the funds the equivalent converter forwards, and the
+    // equivalent converter has already burned the user's basset, so a refund goes to the user
+    assert_access_allowed(deps.as_ref(), recipient.as_str(), recipient.as_str())?;
+    let sender = info.sender.clone();
+    convert_deposit(
+        deps,
+        env,
+        info,
+        sender,
+        recipient.clone(),
+        recipient,
+        limits,
+    )
+}
+
+pub const BACKING_COVERS_SUPPLY: &str = "backing_covers_supply";
+
+/// Checks that the backing after a conversion still covers the basset supply after it,
+/// and describes the broken invariant otherwise. The native value conversions rounded in the
+/// user's favor gave away, including `deficit` of this conversion, is tolerated.
+fn backing_violation(
+    deps: Deps,
+    config: &Config,
+    backing: Uint128,
+    minted: Uint128,
+    burned: Uint128,
+    deficit: Uint128,
+) -> StdResult<Option<Event>> {
+    let (supply, basset_decimals) = basset_supply(deps, config)?;
+    let supply = supply.checked_add(minted)?.saturating_sub(burned);
+    let required = required_backing(
+        supply,
+        basset_decimals,
+        config.denom_decimals.unwrap(),
+        read_exchange_rate(deps.storage)?,
+    )?;
+    let tolerance = read_rounding_deficit(deps.storage)?.checked_add(deficit)?;
+    if backing.checked_add(tolerance)? >= required {
+        return Ok(None);
+    }
+
+    Ok(Some(Event::new("invariant_violation").add_attributes(
+        vec![
+            ("invariant", BACKING_COVERS_SUPPLY),
+            ("backing", &backing.to_string()),
+            ("required_backing", &required.to_string()),
+            ("basset_supply", &supply.to_string()),
+        ],
+    )))
+}
+
+/// Refuses the conversion and pauses the converter until an admin resumes it. The refusal
+/// is not an error, so that the pause is kept; the input is returned instead.
+fn trip_invariant(
+    deps: DepsMut,
+    env: &Env,
+    event: Event,
+    refund: Option<CosmosMsg<TokenFactoryMsg>>,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    store_paused(deps.storage, true)?;
+    log_admin_action(
+        deps,
+        env,
+        &env.contract.address,
+        "auto_pause",
+        &false,
+        &event,
+    )?;
+
+    Ok(Response::new()
+        .add_messages(refund)
+        .add_event(event)
+        .add_attributes(vec![("action", "auto_pause")]))
+}
+
+/// The basset, the tokenfactory denom of the converter or the registered cw20 token.
+fn basset_asset(deps: Deps, config: &Config) -> StdResult<DenomOrCw20> {
+    if let Some(basset) = read_tokenfactory_basset(deps.storage)? {
+        return Ok(DenomOrCw20::Native(basset.denom));
+    }
+    config
+        .basset_token_address
+        .as_ref()
+        .map(|token| {
+            Ok(DenomOrCw20::Cw20(
+                deps.api.addr_humanize(token)?.to_string(),
+            ))
+        })
+        .unwrap_or_else(|| Err(StdError::generic_err("basset must be registered first")))
+}
+
+fn basset_registered(deps: Deps, config: &Config) -> StdResult<bool> {
+    Ok(config.basset_token_address.is_some() || read_tokenfactory_basset(deps.storage)?.is_some())
+}
+
+fn query_basset_supply(deps: Deps, basset: &DenomOrCw20) -> StdResult<Uint128> {
+    match basset {
+        DenomOrCw20::Native(denom) => Ok(deps.querier.query_supply(denom)?.amount),
+        DenomOrCw20::Cw20(token) => {
+            Ok(query_token_info(deps, deps.api.addr_validate(token)?)?.total_supply)
+        }
+    }
+}
+
+/// Total supply and decimals of the basset.
+fn basset_supply(deps: Deps, config: &Config) -> StdResult<(Uint128, u8)> {
+    if let Some(basset) = read_tokenfactory_basset(deps.storage)? {
+        let supply = deps.querier.query_supply(basset.denom)?.amount;
+        return Ok((supply, basset.decimals));
+    }
+    let token = config
+        .basset_token_address
+        .as_ref()
+        .ok_or_else(|| StdError::generic_err("basset must be registered first"))?;
+    let token_info = query_token_info(deps, deps.api.addr_humanize(token)?)?;
+    Ok((token_info.total_supply, token_info.decimals))
+}
+
+/// Asset backing the basset, the registered native denom or a cw20 underlying token.
+fn reserve_asset(deps: Deps, config: &Config) -> StdResult<DenomOrCw20> {
+    match read_cw20_underlying(deps.storage)? {
+        Some(token) => Ok(DenomOrCw20::Cw20(
+            deps.api.addr_humanize(&token)?.to_string(),
+        )),
+        None => config
+            .native_denom
+            .clone()
+            .map(DenomOrCw20::Native)
+            .ok_or_else(|| StdError::generic_err("native denom must be registered first")),
+    }
+}
+
+fn reserve_balance(deps: Deps, env: &Env, reserve: &DenomOrCw20) -> StdResult<Uint128> {
+    match reserve {
+        DenomOrCw20::Native(denom) => Ok(deps
+            .querier
+            .query_balance(env.contract.address.clone(), denom)?
+            .amount),
+        DenomOrCw20::Cw20(token) => query_cw20_balance(
+            deps,
+            deps.api.addr_validate(token)?,
+            env.contract.address.clone(),
+        ),
+    }
+}
+
+/// Sends a bank denom or a cw20 token, such as reserves or escrowed basset.
+fn transfer_msg(
+    asset: &DenomOrCw20,
+    recipient: &str,
+    amount: Uint128,
+) -> StdResult<CosmosMsg<TokenFactoryMsg>> {
+    Ok(match asset {
+        DenomOrCw20::Native(denom) => CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin {
+                denom: denom.clone(),
+                amount,
+            }],
+        }),
+        DenomOrCw20::Cw20(token) => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: token.clone(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }),
+    })
+}
+
+/// Most tickets filled in one go, so a long queue cannot exhaust the gas of a conversion.
+const MAX_TICKETS_FILLED: usize = 30;
+
+/// Fills waiting tickets in order while the reserves not held for filled tickets cover
+/// them, and returns the queue with the reserves that are left.
+fn fill_redemption_queue(
+    deps: DepsMut,
+    env: &Env,
+    reserve: &DenomOrCw20,
+) -> StdResult<(RedemptionQueue, Uint128)> {
+    let mut queue = read_redemption_queue(deps.storage)?;
+    let balance = reserve_balance(deps.as_ref(), env, reserve)?;
+    let mut available = balance.saturating_sub(queue.reserved);
+    if queue.head == queue.next_id {
+        return Ok((queue, available));
+    }
+
+    let mut head = queue.next_id;
+    for mut ticket in read_redemption_tickets_from(deps.storage, queue.head, MAX_TICKETS_FILLED)? {
+        if ticket.native_amount > available {
+            head = ticket.id;
+            break;
+        }
+        available -= ticket.native_amount;
+        queue.reserved += ticket.native_amount;
+        ticket.filled = true;
+        store_redemption_ticket(deps.storage, &ticket)?;
+        head = ticket.id + 1;
+    }
+    queue.head = head;
+    store_redemption_queue(deps.storage, &queue)?;
+
+    Ok((queue, available))
+}
+
+/// Keeps the basset in escrow and records a ticket for the redemption.
+fn queue_redemption(
+    deps: DepsMut,
+    mut queue: RedemptionQueue,
+    sender: String,
+    basset_amount: Uint128,
+    native_amount: Uint128,
+    dust: Uint128,
+    rounding_deficit: Uint128,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    let ticket = RedemptionTicket {
+        id: queue.next_id,
+        owner: deps.api.addr_canonicalize(&sender)?,
+        basset_amount,
+        native_amount,
+        filled: false,
+        dust,
+        rounding_deficit,
+    };
+    queue.next_id += 1;
+    queue.escrowed = queue.escrowed.checked_add(basset_amount)?;
+    store_redemption_ticket(deps.storage, &ticket)?;
+    store_redemption_queue(deps.storage, &queue)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "queue_redemption"),
+        ("ticket_id", &ticket.id.to_string()),
+        ("owner", &sender),
+        ("basset_amount", &basset_amount.to_string()),
+        ("native_amount", &native_amount.to_string()),
+    ]))
+}
+
+pub fn claim_redemption(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    assert_not_paused(deps.as_ref())?;
+    let config = read_config(deps.storage)?;
+    let reserve = reserve_asset(deps.as_ref(), &config)?;
+
+    let (mut queue, _) = fill_redemption_queue(deps.branch(), &env, &reserve)?;
+    let ticket = read_redemption_ticket(deps.storage, id)?;
+    if deps.api.addr_canonicalize(info.sender.as_str())? != ticket.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+    if !ticket.filled {
+        return Err(StdError::generic_err(format!(
+            "redemption ticket {} is not filled yet",
+            id
+        )));
+    }
+
+    let backing = read_backing(deps.storage)?.saturating_sub(ticket.native_amount);
+    if let Some(event) = backing_violation(
+        deps.as_ref(),
+        &config,
+        backing,
+        Uint128::zero(),
+        ticket.basset_amount,
+        ticket.rounding_deficit,
+    )? {
+        return trip_invariant(deps, &env, event, None);
+    }
+    store_backing(deps.storage, backing)?;
+    record_rounding_deficit(deps.storage, ticket.rounding_deficit)?;
+
+    queue.reserved = queue.reserved.checked_sub(ticket.native_amount)?;
+    queue.escrowed = queue.escrowed.checked_sub(ticket.basset_amount)?;
+    store_redemption_queue(deps.storage, &queue)?;
+    remove_redemption_ticket(deps.storage, &ticket);
+
+    let event = convert_event(
+        DIRECTION_TO_NATIVE,
+        &pair_name(deps.as_ref(), &config)?,
+        info.sender.as_str(),
+        info.sender.as_str(),
+        ticket.basset_amount,
+        ticket.native_amount,
+        ticket.dust,
+    );
+    let basset = basset_asset(deps.as_ref(), &config)?;
+    let burn_msg = supply_change_msg(
+        deps.branch(),
+        &basset,
+        Cw20ExecuteMsg::Burn {
+            amount: ticket.basset_amount,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_event(event)
+        .add_message(transfer_msg(
+            &reserve,
+            info.sender.as_str(),
+            ticket.native_amount,
+        )?)
+        .add_submessages(burn_msg)
+        .add_attributes(vec![
+            ("action", "claim_redemption"),
+            ("ticket_id", &id.to_string()),
+            ("recipient", info.sender.as_str()),
+            ("return_amount", &ticket.native_amount.to_string()),
+            ("burn_amount", &ticket.basset_amount.to_string()),
+        ]))
+}
+
+pub fn cancel_redemption(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> StdResult<Response<TokenFactoryMsg>> {
+    let config = read_config(deps.storage)?;
+    let ticket = read_redemption_ticket(deps.storage, id)?;
+    if deps.api.addr_canonicalize(info.sender.as_str())? != ticket.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+    if ticket.filled {
+        return Err(StdError::generic_err(format!(
+            "redemption ticket {} is already filled, claim it instead",
+            id
+        )));
+    }
+    let mut queue = read_redemption_queue(deps.storage)?;
+    queue.escrowed = queue.escrowed.checked_sub(ticket.basset_amount)?;
+    store_redemption_queue(deps.storage, &queue)?;
+    remove_redemption_ticket(deps.storage, &ticket);
+
+    Ok(Response::new()
+        .add_message(transfer_msg(
+            &basset_asset(deps.as_ref(), &config)?,
+            info.sender.as_str(),
+            ticket.basset_amount,
+        )?)
+        .add_attributes(vec![
+            ("action", "cancel_redemption"),
+            ("ticket_id", &id.to_string()),
+            ("owner", info.sender.as_str()),
+            ("basset_amount", &ticket.basset_amount.to_string()),
+        ]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::NewOwner {} => to_json_binary(&query_new_owner(deps)?),
+        QueryMsg::AccessMode {} => to_json_binary(&query_access_mode(deps)?),
+        QueryMsg::Allowlist { start_after, limit } => to_json_binary(&query_address_list(
+            deps,
+            PREFIX_ALLOWLIST,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::Denylist { start_after, limit } => to_json_binary(&query_address_list(
+            deps,
+            PREFIX_DENYLIST,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::Roles {} => to_json_binary(&query_roles(deps)?),
+        QueryMsg::ExchangeRate {} => to_json_binary(&query_exchange_rate(deps)?),
+        QueryMsg::SimulateConvertToBasset { amount } => {
+            to_json_binary(&query_simulate_convert(deps, amount, true)?)
+        }
+        QueryMsg::SimulateConvertToNative { amount } => {
+            to_json_binary(&query_simulate_convert(deps, amount, false)?)
+        }
+        QueryMsg::RedemptionTicket { id } => to_json_binary(&query_redemption_ticket(deps, id)?),
+        QueryMsg::RedemptionTickets {
+            owner,
+            start_after,
+            limit,
+        } => to_json_binary(&query_redemption_tickets(deps, owner, start_after, limit)?),
+        QueryMsg::Strategy {} => to_json_binary(&query_strategy(deps)?),
+        QueryMsg::Invariants {} => to_json_binary(&query_invariants(deps, env)?),
+        QueryMsg::Council {} => to_json_binary(&query_council(deps)?),
+        QueryMsg::Proposal { id } => to_json_binary(&query_proposal(deps, env, id)?),
+        QueryMsg::Proposals { start_after, limit } => {
+            to_json_binary(&query_proposals(deps, env, start_after, limit)?)
+        }
+        QueryMsg::AdminLog { start_after, limit } => {
+            to_json_binary(&query_admin_log(deps, start_after, limit)?)
+        }
+        QueryMsg::EquivalentPairs { start_after, limit } => {
+            to_json_binary(&query_equivalent_pairs(deps, start_after, limit)?)
+        }
+    }
+}
+
+pub fn query_equivalent_pairs(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<EquivalentPairsResponse> {
+    let start_after = match start_after {
+        Some(basset_token) => Some(deps.api.addr_canonicalize(&basset_token)?),
+        None => None,
+    };
+    Ok(EquivalentPairsResponse {
+        pairs: read_equivalent_pairs(deps.storage, deps.api, start_after, limit)?,
+    })
+}
+
+pub fn query_admin_log(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<AdminLogResponse> {
+    let entries = read_admin_log(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|entry| {
+            Ok(AdminLogEntryResponse {
+                id: entry.id,
+                sender: deps.api.addr_humanize(&entry.sender)?.to_string(),
+                action: entry.action,
+                old_value: entry.old_value,
+                new_value: entry.new_value,
+                time: entry.time,
+                proposal_id: entry.proposal_id,
+                approvers: entry
+                    .approvers
+                    .iter()
+                    .map(|addr| Ok(deps.api.addr_humanize(addr)?.to_string()))
+                    .collect::<StdResult<Vec<String>>>()?,
+            })
+        })
+        .collect::<StdResult<Vec<AdminLogEntryResponse>>>()?;
+    Ok(AdminLogResponse { entries })
+}
+
+pub fn query_council(deps: Deps) -> StdResult<CouncilResponse> {
+    let council = match read_council(deps.storage)? {
+        Some(council) => Some(Council {
+            members: council
+                .members
+                .iter()
+                .map(|member| Ok(deps.api.addr_humanize(member)?.to_string()))
+                .collect::<StdResult<Vec<String>>>()?,
+            threshold: council.threshold,
+            voting_period: council.voting_period,
+        }),
+        None => None,
+    };
+    Ok(CouncilResponse { council })
+}
+
+fn to_proposal_response(deps: Deps, env: &Env, proposal: Proposal) -> StdResult<ProposalResponse> {
+    let humanize = |addrs: &[CanonicalAddr]| {
+        addrs
+            .iter()
+            .map(|addr| Ok(deps.api.addr_humanize(addr)?.to_string()))
+            .collect::<StdResult<Vec<String>>>()
+    };
+    let replaced = proposal.council_epoch != read_council_epoch(deps.storage)?;
+    let status = match proposal.status {
+        ProposalStatus::Open | ProposalStatus::Passed
+            if replaced || env.block.time.seconds() >= proposal.expires_at =>
+        {
+            ProposalStatus::Expired
+        }
+        status => status,
+    };
+    Ok(ProposalResponse {
+        id: proposal.id,
+        proposer: deps.api.addr_humanize(&proposal.proposer)?.to_string(),
+        action: proposal.action,
+        approvals: humanize(&proposal.approvals)?,
+        rejections: humanize(&proposal.rejections)?,
+        expires_at: proposal.expires_at,
+        status,
+    })
+}
+
+pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<ProposalResponse> {
+    to_proposal_response(deps, &env, read_proposal(deps.storage, id)?)
+}
+
+pub fn query_proposals(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ProposalsResponse> {
+    let proposals = read_proposals(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|proposal| to_proposal_response(deps, &env, proposal))
+        .collect::<StdResult<Vec<ProposalResponse>>>()?;
+    Ok(ProposalsResponse { proposals })
+}
+
+pub fn query_invariants(deps: Deps, env: Env) -> StdResult<InvariantsResponse> {
+    let config = read_config(deps.storage)?;
+    let denom_decimals = match (config.native_denom.as_ref(), config.denom_decimals) {
+        (Some(_), Some(denom_decimals)) if basset_registered(deps, &config)? => denom_decimals,
+        _ => {
+            return Err(StdError::generic_err(
+                "native or basset token must be registered first",
+            ))
+        }
+    };
+
+    let (basset_supply, basset_decimals) = basset_supply(deps, &config)?;
+    let required_backing = required_backing(
+        basset_supply,
+        basset_decimals,
+        denom_decimals,
+        read_exchange_rate(deps.storage)?,
+    )?;
+    let bank_balance = reserve_balance(deps, &env, &reserve_asset(deps, &config)?)?;
+    let strategy_principal = read_strategy_principal(deps.storage)?;
+    let backing = read_backing(deps.storage)?;
+    let reserves = bank_balance.checked_add(strategy_principal)?;
+
+    Ok(InvariantsResponse {
+        backing,
+        bank_balance,
+        strategy_principal,
+        basset_supply,
+        required_backing,
+        unaccounted_reserves: reserves.saturating_sub(backing),
+        missing_reserves: backing.saturating_sub(reserves),
+        unbacked_supply: required_backing.saturating_sub(backing),
+        rounding_deficit: read_rounding_deficit(deps.storage)?,
+    })
+}
+
+pub fn query_strategy(deps: Deps) -> StdResult<StrategyResponse> {
+    let principal = read_strategy_principal(deps.storage)?;
+    let strategy = to_strategy_config(deps, read_strategy(deps.storage)?)?;
+    let total_value = match &strategy {
+        Some(strategy) => query_strategy_value(deps, Addr::unchecked(&strategy.strategy))?,
+        None => Uint128::zero(),
+    };
+
+    Ok(StrategyResponse {
+        strategy,
+        principal,
+        total_value,
+        accrued_yield: total_value.saturating_sub(principal),
+    })
+}
+
+fn to_ticket_response(
+    deps: Deps,
+    queue: &RedemptionQueue,
+    ticket: RedemptionTicket,
+) -> StdResult<RedemptionTicketResponse> {
+    let queue_position = if ticket.filled {
         None
+    } else {
+        Some(read_queue_position(deps.storage, queue.head, ticket.id)?)
+    };
+    Ok(RedemptionTicketResponse {
+        id: ticket.id,
+        owner: deps.api.addr_humanize(&ticket.owner)?.to_string(),
+        basset_amount: ticket.basset_amount,
+        native_amount: ticket.native_amount,
+        filled: ticket.filled,
+        queue_position,
+    })
+}
+
+pub fn query_redemption_ticket(deps: Deps, id: u64) -> StdResult<RedemptionTicketResponse> {
+    let queue = read_redemption_queue(deps.storage)?;
+    to_ticket_response(deps, &queue, read_redemption_ticket(deps.storage, id)?)
+}
+
+pub fn query_redemption_tickets(
+    deps: Deps,
+    owner: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<RedemptionTicketsResponse> {
+    let queue = read_redemption_queue(deps.storage)?;
+    let owner_raw = deps.api.addr_canonicalize(&owner)?;
+    let tickets = read_user_tickets(deps.storage, &owner_raw, start_after, limit)?
+        .into_iter()
+        .map(|ticket| to_ticket_response(deps, &queue, ticket))
+        .collect::<StdResult<Vec<RedemptionTicketResponse>>>()?;
+
+    Ok(RedemptionTicketsResponse { tickets })
+}
+
+pub fn query_simulate_convert(
+    deps: Deps,
+    amount: Uint128,
+    to_basset: bool,
+) -> StdResult<SimulationResponse> {
+    let config = read_config(deps.storage)?;
+    if !basset_registered(deps, &config)? || config.native_denom.is_none() {
+        return Err(StdError::generic_err(
+            "native or basset token must be registered first",
+        ));
+    }
+    let amount = if to_basset {
+        basset_amount_for(deps, &config, amount, read_rounding_mode(deps.storage)?)?
+    } else {
+        native_amount_for(deps, &config, amount, read_rounding_mode(deps.storage)?)?
+    };
+
+    Ok(SimulationResponse {
+        amount,
+        rounding_mode: read_rounding_mode(deps.storage)?,
+    })
+}
+
+pub fn query_exchange_rate(deps: Deps) -> StdResult<ExchangeRateResponse> {
+    let oracle = match read_rate_oracle(deps.storage)? {
+        Some(oracle) => Some(deps.api.addr_humanize(&oracle)?.to_string()),
+        None => None,
+    };
+    let price_feed = match read_price_feed(deps.storage)? {
+        Some(price_feed) => Some(PriceFeed {
+            oracle: deps.api.addr_humanize(&price_feed.oracle)?.to_string(),
+            base: price_feed.base,
+            quote: price_feed.quote,
+            max_age: price_feed.max_age,
+            max_deviation: price_feed.max_deviation,
+        }),
+        None => None,
+    };
+    Ok(ExchangeRateResponse {
+        exchange_rate: read_exchange_rate(deps.storage)?,
+        last_updated: read_rate_last_updated(deps.storage)?,
+        oracle,
+        price_feed,
+    })
+}
+
+pub fn query_roles(deps: Deps) -> StdResult<RolesResponse> {
+    let roles = Role::ALL
+        .iter()
+        .map(|role| {
+            Ok(RoleMembers {
+                role: *role,
+                addresses: read_role_members(deps.storage, deps.api, *role)?,
+            })
+        })
+        .collect::<StdResult<Vec<RoleMembers>>>()?;
+
+    Ok(RolesResponse { roles })
+}
+
+pub fn query_access_mode(deps: Deps) -> StdResult<AccessModeResponse> {
+    Ok(AccessModeResponse {
+        mode: read_access_mode(deps.storage)?,
+    })
+}
+
+pub fn query_address_list(
+    deps: Deps,
+    prefix: &[u8],
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AddressListResponse> {
+    let start_after = match start_after {
+        Some(address) => Some(deps.api.addr_canonicalize(&address)?),
+        None => None,
+    };
+    Ok(AddressListResponse {
+        addresses: read_listed_addresses(deps.storage, deps.api, prefix, start_after, limit)?,
+    })
+}
+
+pub fn query_new_owner(deps: Deps) -> StdResult<NewOwnerResponse> {
+    let new_owner = read_new_owner(deps.storage)?;
+    Ok(NewOwnerResponse {
+        new_owner: deps
+            .api
+            .addr_humanize(&new_owner.new_owner_addr)?
+            .to_string(),
+    })
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = read_config(deps.storage)?;
+    let basset_token = match config.basset_token_address {
+        Some(basset_token_address) => {
+            Some(deps.api.addr_humanize(&basset_token_address)?.to_string())
+        }
+        None => None,
+    };
+    let custody_contract = match read_custody(deps.storage)? {
+        Some(custody) => Some(deps.api.addr_humanize(&custody)?.to_string()),
+        None => None,
     };
     Ok(ConfigResponse {
         owner: deps.api.addr_humanize(&config.owner)?.to_string(),
         basset_token_address: basset_token,
-        native_denom,
+        native_denom: config.native_denom,
+        custody_contract,
+        paused: read_paused(deps.storage)?,
+        rounding_mode: read_rounding_mode(deps.storage)?,
+        mode: read_conversion_mode(deps.storage)?,
+        cw20_underlying: read_cw20_underlying(deps.storage)?.is_some(),
+        basset_denom: read_tokenfactory_basset(deps.storage)?.map(|basset| basset.denom),
     })
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response<TokenFactoryMsg>> {
+    // converters from before the backing ledger start out with the backing their supply needs
+    let config = read_config(deps.storage)?;
+    if let (Some(basset_token_address), Some(denom_decimals)) =
+        (config.basset_token_address, config.denom_decimals)
+    {
+        if read_backing(deps.storage)?.is_zero() {
+            let token_info = query_token_info(
+                deps.as_ref(),
+                deps.api.addr_humanize(&basset_token_address)?,
+            )?;
+            let backing = required_backing(
+                token_info.total_supply,
+                token_info.decimals,
+                denom_decimals,
+                read_exchange_rate(deps.storage)?,
+            )?;
+            store_backing(deps.storage, backing)?;
+        }
+    }
     Ok(Response::default())
 }
@@ -0,0 +1,109 @@
+use cosmwasm_std::{Binary, Coin, CosmosMsg, StdError, StdResult, Timestamp};
+
+const MSG_TRANSFER_TYPE_URL: &str = "/ibc.applications.transfer.v1.MsgTransfer";
+const TRANSFER_PORT: &str = "transfer";
+
+const WIRE_VARINT: u64 = 0;
+const WIRE_FIXED64: u64 = 1;
+const WIRE_LENGTH_DELIMITED: u64 = 2;
+const WIRE_FIXED32: u64 = 5;
+
+/// Ics20 transfer whose ack or timeout ibc-hooks reports to `callback` through sudo.
+/// `IbcMsg::Transfer` cannot carry the memo that asks for the callback.
+pub fn transfer_with_callback<T>(
+    channel_id: &str,
+    sender: &str,
+    receiver: &str,
+    amount: &Coin,
+    timeout: Timestamp,
+    callback: &str,
+) -> CosmosMsg<T> {
+    let mut token = vec![];
+    encode_bytes(&mut token, 1, amount.denom.as_bytes());
+    encode_bytes(&mut token, 2, amount.amount.to_string().as_bytes());
+
+    let mut value = vec![];
+    encode_bytes(&mut value, 1, TRANSFER_PORT.as_bytes());
+    encode_bytes(&mut value, 2, channel_id.as_bytes());
+    encode_bytes(&mut value, 3, &token);
+    encode_bytes(&mut value, 4, sender.as_bytes());
+    encode_bytes(&mut value, 5, receiver.as_bytes());
+    encode_varint(&mut value, 7 << 3 | WIRE_VARINT);
+    encode_varint(&mut value, timeout.nanos());
+    encode_bytes(
+        &mut value,
+        8,
+        format!("{{\"ibc_callback\":\"{}\"}}", callback).as_bytes(),
+    );
+
+    CosmosMsg::Stargate {
+        type_url: MSG_TRANSFER_TYPE_URL.to_string(),
+        value: Binary::from(value),
+    }
+}
+
+/// Packet sequence of a sent transfer, read from the MsgTransferResponse of its reply.
+pub fn transfer_sequence(data: Option<&Binary>) -> StdResult<u64> {
+    let mut data = data
+        .ok_or_else(|| StdError::generic_err("transfer reply has no data"))?
+        .as_slice();
+    while !data.is_empty() {
+        let key = decode_varint(&mut data)?;
+        match (key >> 3, key & 7) {
+            (1, WIRE_VARINT) => return decode_varint(&mut data),
+            (_, WIRE_VARINT) => {
+                decode_varint(&mut data)?;
+            }
+            (_, WIRE_LENGTH_DELIMITED) => {
+                let len = decode_varint(&mut data)? as usize;
+                data = data.get(len..).ok_or_else(truncated)?;
+            }
+            (_, WIRE_FIXED64) => data = data.get(8..).ok_or_else(truncated)?,
+            (_, WIRE_FIXED32) => data = data.get(4..).ok_or_else(truncated)?,
+            _ => {
+                return Err(StdError::parse_err(
+                    "MsgTransferResponse",
+                    "unknown wire type",
+                ))
+            }
+        }
+    }
+    Err(StdError::parse_err(
+        "MsgTransferResponse",
+        "missing sequence",
+    ))
+}
+
+fn encode_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn encode_bytes(buf: &mut Vec<u8>, field: u64, bytes: &[u8]) {
+    encode_varint(buf, field << 3 | WIRE_LENGTH_DELIMITED);
+    encode_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+fn decode_varint(data: &mut &[u8]) -> StdResult<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (byte, rest) = data.split_first().ok_or_else(truncated)?;
+        *data = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(StdError::parse_err(
+        "MsgTransferResponse",
+        "varint overflow",
+    ))
+}
+
+fn truncated() -> StdError {
+    StdError::parse_err("MsgTransferResponse", "truncated")
+}
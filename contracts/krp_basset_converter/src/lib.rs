@@ -1,4 +1,5 @@
 pub mod contract;
+pub mod ibc;
 pub mod math;
 pub mod querier;
 pub mod state;
@@ -1,25 +1,44 @@
-use cosmwasm_std::{StdError, StdResult, Uint128};
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use basset::converter::RoundingMode;
+use cosmwasm_bignumber::{Decimal256, Uint256 as BigUint256};
+use cosmwasm_std::{StdError, StdResult, Uint128, Uint256};
+
+const DECIMAL_FRACTIONAL: u128 = 1_000_000_000_000_000_000;
+
+/// Largest decimals accepted for either side of a pair, which keeps every scaling factor
+/// and the intermediate products of a conversion within Uint256.
+pub(crate) const MAX_DECIMALS: u8 = 18;
+
+pub(crate) fn assert_supported_decimals(basset_decimals: u8, denom_decimals: u8) -> StdResult<()> {
+    if basset_decimals > MAX_DECIMALS || denom_decimals > MAX_DECIMALS {
+        return Err(StdError::generic_err(format!(
+            "unsupported decimals; basset {} and native {} must not exceed {}",
+            basset_decimals, denom_decimals, MAX_DECIMALS
+        )));
+    }
+    Ok(())
+}
 
 pub(crate) fn convert_to_denom_decimals(
     amount: Uint128,
     basset_decimals: u8,
     denom_decimals: u8,
+    rounding: RoundingMode,
 ) -> StdResult<Uint128> {
+    let decimal_fraction = decimal_fraction(basset_decimals, denom_decimals)?;
     if basset_decimals > denom_decimals {
-        let decimal_fraction =
-            Uint128::new(10u128).saturating_pow((basset_decimals - denom_decimals) as u32);
-        let result = amount.checked_div(decimal_fraction);
-        if result.as_ref().unwrap().is_zero() {
+        let result = to_uint128(div_rounded(amount.into(), decimal_fraction, rounding)?)?;
+        if result.is_zero() {
             return Err(StdError::generic_err(format!(
                 "cannot convert; conversion is only possible for amounts greater than {} basset token",
                 decimal_fraction
             )));
         }
-        Ok(result.unwrap())
+        Ok(result)
     } else {
-        let decimal_fraction =
-            Uint128::new(10u128).saturating_pow((denom_decimals - basset_decimals) as u32);
-        Ok(amount.checked_mul(decimal_fraction).unwrap())
+        to_uint128(Uint256::from(amount).checked_mul(decimal_fraction)?)
     }
 }
 
@@ -27,36 +46,155 @@ pub(crate) fn convert_to_basset_decimals(
     amount: Uint128,
     basset_decimals: u8,
     denom_decimals: u8,
+    rounding: RoundingMode,
 ) -> StdResult<Uint128> {
+    let decimal_fraction = decimal_fraction(basset_decimals, denom_decimals)?;
     if basset_decimals > denom_decimals {
-        let decimal_fraction =
-            Uint128::new(10u128).saturating_pow((basset_decimals - denom_decimals) as u32);
-
-        Ok(amount.checked_mul(decimal_fraction).unwrap())
+        to_uint128(Uint256::from(amount).checked_mul(decimal_fraction)?)
     } else {
-        let decimal_fraction =
-            Uint128::new(10u128).saturating_pow((denom_decimals - basset_decimals) as u32);
-        let result = amount.checked_div(decimal_fraction);
-        if result.as_ref().unwrap().is_zero() {
+        let result = to_uint128(div_rounded(amount.into(), decimal_fraction, rounding)?)?;
+        if result.is_zero() {
             return Err(StdError::generic_err(format!(
                 "cannot convert; conversion is only possible for amounts greater than {} native token",
                 decimal_fraction
             )));
         }
-        Ok(result.unwrap())
+        Ok(result)
+    }
+}
+
+/// Basset minted for a native amount already rescaled to basset decimals.
+/// The exchange rate is the amount of basset one native token is worth.
+pub(crate) fn apply_exchange_rate(
+    amount: Uint128,
+    exchange_rate: Decimal256,
+    rounding: RoundingMode,
+) -> StdResult<Uint128> {
+    let result = to_uint128(div_rounded(
+        Uint256::from(amount).checked_mul(rate_numerator(exchange_rate)?)?,
+        Uint256::from(DECIMAL_FRACTIONAL),
+        rounding,
+    )?)?;
+    if result.is_zero() {
+        return Err(StdError::generic_err(
+            "cannot convert; the amount is too small for the current exchange rate",
+        ));
+    }
+    Ok(result)
+}
+
+/// Native amount, still in basset decimals, returned for a basset amount.
+pub(crate) fn remove_exchange_rate(
+    amount: Uint128,
+    exchange_rate: Decimal256,
+    rounding: RoundingMode,
+) -> StdResult<Uint128> {
+    if exchange_rate.is_zero() {
+        return Err(StdError::generic_err(
+            "exchange rate must be greater than zero",
+        ));
+    }
+    let result = to_uint128(div_rounded(
+        Uint256::from(amount).checked_mul(Uint256::from(DECIMAL_FRACTIONAL))?,
+        rate_numerator(exchange_rate)?,
+        rounding,
+    )?)?;
+    if result.is_zero() {
+        return Err(StdError::generic_err(
+            "cannot convert; the amount is too small for the current exchange rate",
+        ));
+    }
+    Ok(result)
+}
+
+/// numerator / denominator, rounded according to the given mode
+fn div_rounded(
+    numerator: Uint256,
+    denominator: Uint256,
+    rounding: RoundingMode,
+) -> StdResult<Uint256> {
+    let quotient = numerator.checked_div(denominator)?;
+    let remainder = numerator.checked_rem(denominator)?;
+    if remainder.is_zero() {
+        return Ok(quotient);
+    }
+    let round_up = match rounding {
+        RoundingMode::Floor => false,
+        RoundingMode::Ceil => true,
+        RoundingMode::HalfEven => {
+            let twice_remainder = remainder.checked_mul(Uint256::from(2u8))?;
+            twice_remainder > denominator
+                || (twice_remainder == denominator
+                    && !quotient.checked_rem(Uint256::from(2u8))?.is_zero())
+        }
+    };
+    if round_up {
+        Ok(quotient.checked_add(Uint256::one())?)
+    } else {
+        Ok(quotient)
+    }
+}
+
+/// the exchange rate scaled by 10^18, so conversions round only once
+fn rate_numerator(exchange_rate: Decimal256) -> StdResult<Uint256> {
+    let numerator: String = (BigUint256::from(DECIMAL_FRACTIONAL) * exchange_rate).into();
+    Uint256::from_str(&numerator)
+}
+
+/// Native amount needed to redeem the whole basset supply. Both the exchange rate and the
+/// decimal conversion are rounded up, so the truncated remainder of a basset unit is still
+/// considered backed.
+pub(crate) fn required_backing(
+    total_supply: Uint128,
+    basset_decimals: u8,
+    denom_decimals: u8,
+    exchange_rate: Decimal256,
+) -> StdResult<Uint128> {
+    if exchange_rate.is_zero() {
+        return Err(StdError::generic_err(
+            "exchange rate must be greater than zero",
+        ));
     }
+    let native = to_uint128(div_rounded(
+        Uint256::from(total_supply).checked_mul(Uint256::from(DECIMAL_FRACTIONAL))?,
+        rate_numerator(exchange_rate)?,
+        RoundingMode::Ceil,
+    )?)?;
+
+    let decimal_fraction = decimal_fraction(basset_decimals, denom_decimals)?;
+    if basset_decimals > denom_decimals {
+        to_uint128(div_rounded(
+            native.into(),
+            decimal_fraction,
+            RoundingMode::Ceil,
+        )?)
+    } else {
+        to_uint128(Uint256::from(native).checked_mul(decimal_fraction)?)
+    }
+}
+
+/// 10 to the power of the gap between the two decimals
+fn decimal_fraction(basset_decimals: u8, denom_decimals: u8) -> StdResult<Uint256> {
+    assert_supported_decimals(basset_decimals, denom_decimals)?;
+    let gap = basset_decimals.abs_diff(denom_decimals);
+    Ok(Uint256::from(10u8).checked_pow(gap as u32)?)
+}
+
+fn to_uint128(value: Uint256) -> StdResult<Uint128> {
+    Ok(Uint128::try_from(value)?)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_convert_to_denom_decimals() {
         let a = Uint128::new(100000000);
         let b = 4;
         let c = 6;
-        let d = convert_to_denom_decimals(a, b, c).unwrap();
+        let d = convert_to_denom_decimals(a, b, c, RoundingMode::Floor).unwrap();
         assert_eq!(d, Uint128::new(10000000000));
     }
 
@@ -65,7 +203,202 @@ mod tests {
         let a = Uint128::new(100000000);
         let b = 4;
         let c = 6;
-        let d = convert_to_basset_decimals(a, b, c).unwrap();
+        let d = convert_to_basset_decimals(a, b, c, RoundingMode::Floor).unwrap();
         assert_eq!(d, Uint128::new(1000000));
     }
+
+    #[test]
+    fn test_required_backing() {
+        assert_eq!(
+            required_backing(Uint128::new(1000000), 6, 8, Decimal256::one()).unwrap(),
+            Uint128::new(100000000)
+        );
+        // a remainder smaller than one native unit still needs backing
+        assert_eq!(
+            required_backing(Uint128::new(10000000150), 10, 8, Decimal256::one()).unwrap(),
+            Uint128::new(100000002)
+        );
+        assert_eq!(
+            required_backing(Uint128::zero(), 10, 8, Decimal256::one()).unwrap(),
+            Uint128::zero()
+        );
+    }
+
+    #[test]
+    fn test_exchange_rate_rounding() {
+        let rate = Decimal256::from_ratio(3u64, 2u64);
+        // 7 * 1.5 = 10.5 minted as 10
+        assert_eq!(
+            apply_exchange_rate(Uint128::new(7), rate, RoundingMode::Floor).unwrap(),
+            Uint128::new(10)
+        );
+        // 10 / 1.5 = 6.67 returned as 6
+        assert_eq!(
+            remove_exchange_rate(Uint128::new(10), rate, RoundingMode::Floor).unwrap(),
+            Uint128::new(6)
+        );
+        // backing 10 basset at 1.5 needs 7 native, rounded up
+        assert_eq!(
+            required_backing(Uint128::new(10), 6, 6, rate).unwrap(),
+            Uint128::new(7)
+        );
+        assert_eq!(
+            apply_exchange_rate(
+                Uint128::new(1),
+                Decimal256::percent(50),
+                RoundingMode::Floor
+            )
+            .unwrap_err(),
+            StdError::generic_err(
+                "cannot convert; the amount is too small for the current exchange rate"
+            )
+        );
+    }
+
+    #[test]
+    fn test_rounding_modes() {
+        let rate = Decimal256::from_ratio(3u64, 2u64);
+        // 7 * 1.5 = 10.5
+        assert_eq!(
+            apply_exchange_rate(Uint128::new(7), rate, RoundingMode::Ceil).unwrap(),
+            Uint128::new(11)
+        );
+        assert_eq!(
+            apply_exchange_rate(Uint128::new(7), rate, RoundingMode::HalfEven).unwrap(),
+            Uint128::new(10)
+        );
+        // 9 * 1.5 = 13.5
+        assert_eq!(
+            apply_exchange_rate(Uint128::new(9), rate, RoundingMode::HalfEven).unwrap(),
+            Uint128::new(14)
+        );
+        // 10 / 1.5 = 6.67
+        assert_eq!(
+            remove_exchange_rate(Uint128::new(10), rate, RoundingMode::HalfEven).unwrap(),
+            Uint128::new(7)
+        );
+        // 1050 / 10^2 = 10.5, 1051 / 10^2 = 10.51
+        assert_eq!(
+            convert_to_basset_decimals(Uint128::new(1050), 6, 8, RoundingMode::HalfEven).unwrap(),
+            Uint128::new(10)
+        );
+        assert_eq!(
+            convert_to_denom_decimals(Uint128::new(1051), 8, 6, RoundingMode::HalfEven).unwrap(),
+            Uint128::new(11)
+        );
+        assert_eq!(
+            convert_to_denom_decimals(Uint128::new(1001), 8, 6, RoundingMode::Ceil).unwrap(),
+            Uint128::new(11)
+        );
+    }
+
+    #[test]
+    fn test_unsupported_decimals() {
+        let err = convert_to_basset_decimals(Uint128::new(1), 19, 0, RoundingMode::Floor);
+        assert_eq!(
+            err.unwrap_err(),
+            StdError::generic_err(
+                "unsupported decimals; basset 19 and native 0 must not exceed 18"
+            )
+        );
+        assert!(assert_supported_decimals(0, 255).is_err());
+        assert!(required_backing(Uint128::new(1), 18, 40, Decimal256::one()).is_err());
+    }
+
+    fn amounts() -> impl Strategy<Value = Uint128> {
+        prop_oneof![1u128..1_000_000_000_000, 1u128..=u128::MAX].prop_map(Uint128::new)
+    }
+
+    proptest! {
+        // every case walks all 361 decimal pairs
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn test_decimal_pairs(amount in amounts(), rate in 1u64..10_000_000) {
+            let modes = [
+                RoundingMode::Floor,
+                RoundingMode::Ceil,
+                RoundingMode::HalfEven,
+            ];
+            let exchange_rate = Decimal256::from_ratio(rate, 1_000_000u64);
+
+            for basset_decimals in 0..=MAX_DECIMALS {
+                for denom_decimals in 0..=MAX_DECIMALS {
+                    let results = modes.map(|mode| {
+                        convert_to_basset_decimals(amount, basset_decimals, denom_decimals, mode)
+                    });
+                    let redeemed = modes.map(|mode| {
+                        convert_to_denom_decimals(amount, basset_decimals, denom_decimals, mode)
+                    });
+
+                    for results in [&results, &redeemed] {
+                        match results {
+                            [Ok(floor), Ok(ceil), Ok(half_even)] => {
+                                prop_assert!(floor <= half_even && half_even <= ceil);
+                                prop_assert!(ceil.u128() - floor.u128() <= 1);
+                            }
+                            // only overflows or amounts below one unit can fail
+                            [Err(floor), _, _] => prop_assert!(
+                                matches!(floor, StdError::ConversionOverflow { .. })
+                                    || floor.to_string().contains("cannot convert"),
+                                "{}",
+                                floor
+                            ),
+                            results => panic!("{:?}", results),
+                        }
+                    }
+
+                    // a round trip rounded down never returns more than was deposited
+                    if let Ok(basset) = &results[0] {
+                        if let Ok(native) = convert_to_denom_decimals(
+                            *basset,
+                            basset_decimals,
+                            denom_decimals,
+                            RoundingMode::Floor,
+                        ) {
+                            prop_assert!(native <= amount);
+                        }
+                    }
+
+                    // the backing of a supply covers redeeming all of it
+                    match required_backing(amount, basset_decimals, denom_decimals, exchange_rate) {
+                        Ok(backing) => {
+                            if let Ok(native) = remove_exchange_rate(
+                                amount,
+                                exchange_rate,
+                                RoundingMode::Floor,
+                            )
+                            .and_then(|native| {
+                                convert_to_denom_decimals(
+                                    native,
+                                    basset_decimals,
+                                    denom_decimals,
+                                    RoundingMode::Floor,
+                                )
+                            }) {
+                                prop_assert!(native <= backing);
+                            }
+                        }
+                        Err(err) => {
+                            prop_assert!(
+                                matches!(err, StdError::ConversionOverflow { .. }),
+                                "{}",
+                                err
+                            )
+                        }
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn test_exchange_rate_round_trip(amount in amounts(), rate in 1u64..10_000_000) {
+            let exchange_rate = Decimal256::from_ratio(rate, 1_000_000u64);
+            if let Ok(basset) = apply_exchange_rate(amount, exchange_rate, RoundingMode::Floor) {
+                if let Ok(native) = remove_exchange_rate(basset, exchange_rate, RoundingMode::Floor) {
+                    prop_assert!(native <= amount);
+                }
+            }
+        }
+    }
 }
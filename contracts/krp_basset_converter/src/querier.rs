@@ -1,15 +1,56 @@
-use cosmwasm_std::{to_json_binary, Addr, Deps, QueryRequest, StdResult, WasmQuery};
-use cw20::{Cw20QueryMsg, TokenInfoResponse};
+use basset::converter::{
+    ConfigResponse, OraclePriceResponse, OracleQueryMsg, QueryMsg, StrategyQueryMsg,
+    StrategyTotalValueResponse,
+};
+use cosmwasm_std::{to_json_binary, Addr, Deps, QueryRequest, StdResult, Uint128, WasmQuery};
+use cw20::{BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
+
+pub fn query_token_info(deps: Deps, contract_addr: Addr) -> StdResult<TokenInfoResponse> {
+    deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: contract_addr.to_string(),
+        msg: to_json_binary(&Cw20QueryMsg::TokenInfo {})?,
+    }))
+}
+
+pub fn query_cw20_balance(deps: Deps, contract_addr: Addr, address: Addr) -> StdResult<Uint128> {
+    let res: BalanceResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: contract_addr.to_string(),
+        msg: to_json_binary(&Cw20QueryMsg::Balance {
+            address: address.to_string(),
+        })?,
+    }))?;
+    Ok(res.balance)
+}
 
 pub fn query_decimals(deps: Deps, contract_addr: Addr) -> StdResult<u8> {
-    // load price form the oracle
-    let token_info: TokenInfoResponse =
-        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-            contract_addr: contract_addr.to_string(),
-            msg: to_json_binary(&Cw20QueryMsg::TokenInfo {})?,
-        }))?;
+    Ok(query_token_info(deps, contract_addr)?.decimals)
+}
 
-    Ok(token_info.decimals)
+pub fn query_price(
+    deps: Deps,
+    oracle: Addr,
+    base: String,
+    quote: String,
+) -> StdResult<OraclePriceResponse> {
+    deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: oracle.to_string(),
+        msg: to_json_binary(&OracleQueryMsg::Price { base, quote })?,
+    }))
 }
 
+pub fn query_strategy_value(deps: Deps, strategy: Addr) -> StdResult<Uint128> {
+    let res: StrategyTotalValueResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: strategy.to_string(),
+            msg: to_json_binary(&StrategyQueryMsg::TotalValue {})?,
+        }))?;
+    Ok(res.total_value)
+}
 
+/// Config of another converter, such as the converter of an equivalent pair.
+pub fn query_converter_config(deps: Deps, converter: Addr) -> StdResult<ConfigResponse> {
+    deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: converter.to_string(),
+        msg: to_json_binary(&QueryMsg::Config {})?,
+    }))
+}
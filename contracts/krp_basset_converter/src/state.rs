@@ -1,21 +1,194 @@
-use cosmwasm_std::{CanonicalAddr, StdResult, Storage};
-use cosmwasm_storage::{singleton, singleton_read, Singleton, ReadonlySingleton};
+use basset::converter::{
+    AccessMode, ConversionMode, DenomOrCw20, EquivalentPair, ExecuteMsg, ProposalStatus, Role,
+    RoundingMode,
+};
+use cosmwasm_bignumber::Decimal256;
+use cosmwasm_std::{Addr, Api, CanonicalAddr, Coin, Order, StdResult, Storage, Uint128};
+use cosmwasm_storage::{
+    bucket, bucket_read, singleton, singleton_read, Bucket, ReadonlyBucket, ReadonlySingleton,
+    Singleton,
+};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 pub static KEY_CONFIG: &[u8] = b"config";
 const KEY_NEWOWNER: &[u8] = b"newowner";
+const KEY_ACCESS_MODE: &[u8] = b"access_mode";
+const KEY_PAUSED: &[u8] = b"paused";
+const KEY_PENDING_CONVERSION: &[u8] = b"pending_conversion";
+const KEY_PENDING_IBC_REDEMPTION: &[u8] = b"pending_ibc_redemption";
+const KEY_PENDING_HARVEST: &[u8] = b"pending_harvest";
+const KEY_CUSTODY: &[u8] = b"custody";
+const KEY_EXCHANGE_RATE: &[u8] = b"exchange_rate";
+const KEY_RATE_ORACLE: &[u8] = b"rate_oracle";
+const KEY_PRICE_FEED: &[u8] = b"price_feed";
+const KEY_RATE_LAST_UPDATED: &[u8] = b"rate_last_updated";
+const KEY_ROUNDING_MODE: &[u8] = b"rounding_mode";
+const KEY_CONVERSION_MODE: &[u8] = b"conversion_mode";
+const KEY_REDEMPTION_QUEUE: &[u8] = b"redemption_queue";
+const KEY_STRATEGY: &[u8] = b"strategy";
+const KEY_STRATEGY_PRINCIPAL: &[u8] = b"strategy_principal";
+const KEY_BACKING: &[u8] = b"backing";
+const KEY_ROUNDING_DEFICIT: &[u8] = b"rounding_deficit";
+const KEY_COUNCIL: &[u8] = b"council";
+const KEY_COUNCIL_EPOCH: &[u8] = b"council_epoch";
+const KEY_EXECUTING_PROPOSAL: &[u8] = b"executing_proposal";
+const KEY_PROPOSAL_COUNT: &[u8] = b"proposal_count";
+const KEY_ADMIN_LOG_COUNT: &[u8] = b"admin_log_count";
+const KEY_SUPPLY_CHECK: &[u8] = b"supply_check";
+const KEY_CW20_UNDERLYING: &[u8] = b"cw20_underlying";
+const KEY_TOKENFACTORY_BASSET: &[u8] = b"tokenfactory_basset";
+
+pub static PREFIX_ALLOWLIST: &[u8] = b"allowlist";
+pub static PREFIX_DENYLIST: &[u8] = b"denylist";
+static PREFIX_ROLES: &[u8] = b"roles";
+static PREFIX_REDEMPTION_TICKETS: &[u8] = b"redemption_tickets";
+static PREFIX_USER_TICKETS: &[u8] = b"user_tickets";
+static PREFIX_PROPOSALS: &[u8] = b"proposals";
+static PREFIX_ADMIN_LOG: &[u8] = b"admin_log";
+static PREFIX_EQUIVALENT_PAIRS: &[u8] = b"equivalent_pairs";
+static PREFIX_IBC_REDEMPTIONS: &[u8] = b"ibc_redemptions";
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
     pub owner: CanonicalAddr,
     pub basset_token_address: Option<CanonicalAddr>,
     pub native_denom: Option<String>,
-    pub denom_decimals: Option<u8>, 
+    pub denom_decimals: Option<u8>,
 }
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct NewOwnerAddr {
-    pub new_owner_addr: CanonicalAddr, 
+    pub new_owner_addr: CanonicalAddr,
+}
+
+/// Conversion on behalf of a recipient, kept until the reply of its sub message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingConversion {
+    /// sender of the conversion, the sub message is sent by the converter itself
+    pub sender: Addr,
+    pub recipient: Addr,
+    pub funds: Vec<Coin>,
+}
+
+/// Redemption sent over ibc, escrowed until the transfer is acknowledged or times out.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IbcRedemption {
+    /// credited with the native amount if the transfer fails
+    pub owner: Addr,
+    pub channel_id: String,
+    pub amount: Coin,
+}
+
+/// Harvest of the strategy yield, kept until the reply of the strategy withdrawal.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingHarvest {
+    pub recipient: Addr,
+    /// converter balance before the withdrawal
+    pub balance: Coin,
+    pub accrued_yield: Uint128,
+}
+
+/// Basset supplies expected after each pending mint or burn sub message, in execution order.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SupplyCheck {
+    pub basset: DenomOrCw20,
+    pub expected_supplies: Vec<Uint128>,
+}
+
+/// Basset minted as a tokenfactory denom of the converter instead of a cw20 token.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenFactoryBasset {
+    pub denom: String,
+    pub decimals: u8,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PriceFeedConfig {
+    pub oracle: CanonicalAddr,
+    pub base: String,
+    pub quote: String,
+    pub max_age: u64,
+    pub max_deviation: Decimal256,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct RedemptionQueue {
+    pub next_id: u64,
+    /// first ticket that may still be waiting for reserves
+    pub head: u64,
+    /// native amount held for filled tickets that were not claimed yet
+    pub reserved: Uint128,
+    /// basset escrowed for tickets that were not claimed or cancelled yet
+    #[serde(default)]
+    pub escrowed: Uint128,
+}
+
+/// Redemption waiting for reserves, the basset stays escrowed in the converter until
+/// the ticket is claimed or cancelled.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RedemptionTicket {
+    pub id: u64,
+    pub owner: CanonicalAddr,
+    pub basset_amount: Uint128,
+    pub native_amount: Uint128,
+    pub filled: bool,
+    /// basset the rounding of the redemption kept, reported when the ticket is claimed
+    #[serde(default)]
+    pub dust: Uint128,
+    /// native value the rounding of the redemption gave away, tolerated when it is claimed
+    #[serde(default)]
+    pub rounding_deficit: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Strategy {
+    pub strategy: CanonicalAddr,
+    pub max_allocation: Decimal256,
+    pub liquidity_buffer: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CouncilConfig {
+    pub members: Vec<CanonicalAddr>,
+    pub threshold: u32,
+    pub voting_period: u64,
+    /// the converter itself, the sender of executed proposals
+    pub executor: CanonicalAddr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Proposal {
+    pub id: u64,
+    pub proposer: CanonicalAddr,
+    pub action: ExecuteMsg,
+    pub approvals: Vec<CanonicalAddr>,
+    pub rejections: Vec<CanonicalAddr>,
+    pub expires_at: u64,
+    /// Open, Passed, Rejected or Executed, expiry is derived from the block time
+    pub status: ProposalStatus,
+    /// council the proposal was made under, it is void once the council is replaced
+    #[serde(default)]
+    pub council_epoch: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AdminLogEntry {
+    pub id: u64,
+    pub sender: CanonicalAddr,
+    pub action: String,
+    pub old_value: String,
+    pub new_value: String,
+    pub time: u64,
+    /// council proposal the action was executed through
+    #[serde(default)]
+    pub proposal_id: Option<u64>,
+    /// council members that approved the proposal
+    #[serde(default)]
+    pub approvers: Vec<CanonicalAddr>,
 }
 
 pub fn store_new_owner(storage: &mut dyn Storage, data: &NewOwnerAddr) -> StdResult<()> {
@@ -26,10 +199,582 @@ pub fn read_new_owner(storage: &dyn Storage) -> StdResult<NewOwnerAddr> {
     ReadonlySingleton::new(storage, KEY_NEWOWNER).load()
 }
 
-pub fn store_config(storage: &mut dyn Storage) -> Singleton<Config> {
+pub fn store_config(storage: &mut dyn Storage) -> Singleton<'_, Config> {
     singleton(storage, KEY_CONFIG)
 }
 
 pub fn read_config(storage: &dyn Storage) -> StdResult<Config> {
     singleton_read(storage, KEY_CONFIG).load()
 }
+
+pub fn store_access_mode(storage: &mut dyn Storage, mode: &AccessMode) -> StdResult<()> {
+    singleton(storage, KEY_ACCESS_MODE).save(mode)
+}
+
+/// contracts instantiated before access control existed have no mode stored, they are open
+pub fn read_access_mode(storage: &dyn Storage) -> StdResult<AccessMode> {
+    Ok(singleton_read(storage, KEY_ACCESS_MODE)
+        .may_load()?
+        .unwrap_or_default())
+}
+
+pub fn store_listed_address(
+    storage: &mut dyn Storage,
+    prefix: &[u8],
+    address: &CanonicalAddr,
+) -> StdResult<()> {
+    bucket(storage, prefix).save(address.as_slice(), &true)
+}
+
+pub fn remove_listed_address(storage: &mut dyn Storage, prefix: &[u8], address: &CanonicalAddr) {
+    bucket::<bool>(storage, prefix).remove(address.as_slice())
+}
+
+pub fn is_listed_address(
+    storage: &dyn Storage,
+    prefix: &[u8],
+    address: &CanonicalAddr,
+) -> StdResult<bool> {
+    Ok(bucket_read::<bool>(storage, prefix)
+        .may_load(address.as_slice())?
+        .is_some())
+}
+
+pub fn read_listed_addresses(
+    storage: &dyn Storage,
+    api: &dyn Api,
+    prefix: &[u8],
+    start_after: Option<CanonicalAddr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<String>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = calc_range_start(start_after);
+
+    bucket_read::<bool>(storage, prefix)
+        .range(start.as_deref(), None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, _) = item?;
+            Ok(api.addr_humanize(&CanonicalAddr::from(k))?.to_string())
+        })
+        .collect()
+}
+
+pub fn store_paused(storage: &mut dyn Storage, paused: bool) -> StdResult<()> {
+    singleton(storage, KEY_PAUSED).save(&paused)
+}
+
+pub fn read_paused(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(singleton_read(storage, KEY_PAUSED)
+        .may_load()?
+        .unwrap_or_default())
+}
+
+pub fn store_pending_conversion(
+    storage: &mut dyn Storage,
+    pending: &PendingConversion,
+) -> StdResult<()> {
+    singleton(storage, KEY_PENDING_CONVERSION).save(pending)
+}
+
+pub fn read_pending_conversion(storage: &dyn Storage) -> StdResult<PendingConversion> {
+    singleton_read(storage, KEY_PENDING_CONVERSION).load()
+}
+
+pub fn remove_pending_conversion(storage: &mut dyn Storage) {
+    singleton::<PendingConversion>(storage, KEY_PENDING_CONVERSION).remove()
+}
+
+pub fn store_pending_harvest(storage: &mut dyn Storage, harvest: &PendingHarvest) -> StdResult<()> {
+    singleton(storage, KEY_PENDING_HARVEST).save(harvest)
+}
+
+pub fn read_pending_harvest(storage: &dyn Storage) -> StdResult<PendingHarvest> {
+    singleton_read(storage, KEY_PENDING_HARVEST).load()
+}
+
+pub fn remove_pending_harvest(storage: &mut dyn Storage) {
+    singleton::<PendingHarvest>(storage, KEY_PENDING_HARVEST).remove()
+}
+
+/// Keeps the ibc redemption until the reply of its transfer tells the packet sequence.
+pub fn store_pending_ibc_redemption(
+    storage: &mut dyn Storage,
+    redemption: &IbcRedemption,
+) -> StdResult<()> {
+    singleton(storage, KEY_PENDING_IBC_REDEMPTION).save(redemption)
+}
+
+pub fn read_pending_ibc_redemption(storage: &dyn Storage) -> StdResult<IbcRedemption> {
+    singleton_read(storage, KEY_PENDING_IBC_REDEMPTION).load()
+}
+
+pub fn remove_pending_ibc_redemption(storage: &mut dyn Storage) {
+    singleton::<IbcRedemption>(storage, KEY_PENDING_IBC_REDEMPTION).remove()
+}
+
+fn ibc_redemption_key(channel_id: &str, sequence: u64) -> Vec<u8> {
+    [channel_id.as_bytes(), &sequence.to_be_bytes()].concat()
+}
+
+pub fn store_ibc_redemption(
+    storage: &mut dyn Storage,
+    sequence: u64,
+    redemption: &IbcRedemption,
+) -> StdResult<()> {
+    bucket(storage, PREFIX_IBC_REDEMPTIONS).save(
+        &ibc_redemption_key(&redemption.channel_id, sequence),
+        redemption,
+    )
+}
+
+pub fn read_ibc_redemption(
+    storage: &dyn Storage,
+    channel_id: &str,
+    sequence: u64,
+) -> StdResult<Option<IbcRedemption>> {
+    bucket_read(storage, PREFIX_IBC_REDEMPTIONS).may_load(&ibc_redemption_key(channel_id, sequence))
+}
+
+pub fn remove_ibc_redemption(storage: &mut dyn Storage, channel_id: &str, sequence: u64) {
+    bucket::<IbcRedemption>(storage, PREFIX_IBC_REDEMPTIONS)
+        .remove(&ibc_redemption_key(channel_id, sequence))
+}
+
+pub fn store_supply_check(storage: &mut dyn Storage, check: &SupplyCheck) -> StdResult<()> {
+    singleton(storage, KEY_SUPPLY_CHECK).save(check)
+}
+
+pub fn read_supply_check(storage: &dyn Storage) -> StdResult<Option<SupplyCheck>> {
+    singleton_read(storage, KEY_SUPPLY_CHECK).may_load()
+}
+
+pub fn remove_supply_check(storage: &mut dyn Storage) {
+    singleton::<SupplyCheck>(storage, KEY_SUPPLY_CHECK).remove()
+}
+
+/// Set for pairs whose underlying is a cw20 token, the native denom of the config then holds
+/// the token address.
+pub fn store_cw20_underlying(storage: &mut dyn Storage, token: &CanonicalAddr) -> StdResult<()> {
+    singleton(storage, KEY_CW20_UNDERLYING).save(token)
+}
+
+pub fn read_cw20_underlying(storage: &dyn Storage) -> StdResult<Option<CanonicalAddr>> {
+    singleton_read(storage, KEY_CW20_UNDERLYING).may_load()
+}
+
+pub fn store_tokenfactory_basset(
+    storage: &mut dyn Storage,
+    basset: &TokenFactoryBasset,
+) -> StdResult<()> {
+    singleton(storage, KEY_TOKENFACTORY_BASSET).save(basset)
+}
+
+pub fn read_tokenfactory_basset(storage: &dyn Storage) -> StdResult<Option<TokenFactoryBasset>> {
+    singleton_read(storage, KEY_TOKENFACTORY_BASSET).may_load()
+}
+
+pub fn store_custody(storage: &mut dyn Storage, custody: &CanonicalAddr) -> StdResult<()> {
+    singleton(storage, KEY_CUSTODY).save(custody)
+}
+
+pub fn read_custody(storage: &dyn Storage) -> StdResult<Option<CanonicalAddr>> {
+    singleton_read(storage, KEY_CUSTODY).may_load()
+}
+
+pub fn store_exchange_rate(storage: &mut dyn Storage, exchange_rate: &Decimal256) -> StdResult<()> {
+    singleton(storage, KEY_EXCHANGE_RATE).save(exchange_rate)
+}
+
+/// pairs without a stored exchange rate convert at parity
+pub fn read_exchange_rate(storage: &dyn Storage) -> StdResult<Decimal256> {
+    Ok(singleton_read(storage, KEY_EXCHANGE_RATE)
+        .may_load()?
+        .unwrap_or_else(Decimal256::one))
+}
+
+pub fn store_rate_oracle(
+    storage: &mut dyn Storage,
+    oracle: Option<CanonicalAddr>,
+) -> StdResult<()> {
+    match oracle {
+        Some(oracle) => singleton(storage, KEY_RATE_ORACLE).save(&oracle),
+        None => {
+            singleton::<CanonicalAddr>(storage, KEY_RATE_ORACLE).remove();
+            Ok(())
+        }
+    }
+}
+
+pub fn read_rate_oracle(storage: &dyn Storage) -> StdResult<Option<CanonicalAddr>> {
+    singleton_read(storage, KEY_RATE_ORACLE).may_load()
+}
+
+pub fn store_price_feed(
+    storage: &mut dyn Storage,
+    price_feed: Option<PriceFeedConfig>,
+) -> StdResult<()> {
+    match price_feed {
+        Some(price_feed) => singleton(storage, KEY_PRICE_FEED).save(&price_feed),
+        None => {
+            singleton::<PriceFeedConfig>(storage, KEY_PRICE_FEED).remove();
+            Ok(())
+        }
+    }
+}
+
+pub fn read_price_feed(storage: &dyn Storage) -> StdResult<Option<PriceFeedConfig>> {
+    singleton_read(storage, KEY_PRICE_FEED).may_load()
+}
+
+pub fn store_rate_last_updated(storage: &mut dyn Storage, last_updated: u64) -> StdResult<()> {
+    singleton(storage, KEY_RATE_LAST_UPDATED).save(&last_updated)
+}
+
+pub fn read_rate_last_updated(storage: &dyn Storage) -> StdResult<Option<u64>> {
+    singleton_read(storage, KEY_RATE_LAST_UPDATED).may_load()
+}
+
+pub fn store_rounding_mode(storage: &mut dyn Storage, mode: &RoundingMode) -> StdResult<()> {
+    singleton(storage, KEY_ROUNDING_MODE).save(mode)
+}
+
+pub fn read_rounding_mode(storage: &dyn Storage) -> StdResult<RoundingMode> {
+    Ok(singleton_read(storage, KEY_ROUNDING_MODE)
+        .may_load()?
+        .unwrap_or_default())
+}
+
+pub fn store_conversion_mode(storage: &mut dyn Storage, mode: &ConversionMode) -> StdResult<()> {
+    singleton(storage, KEY_CONVERSION_MODE).save(mode)
+}
+
+pub fn read_conversion_mode(storage: &dyn Storage) -> StdResult<ConversionMode> {
+    Ok(singleton_read(storage, KEY_CONVERSION_MODE)
+        .may_load()?
+        .unwrap_or_default())
+}
+
+pub fn store_redemption_queue(storage: &mut dyn Storage, queue: &RedemptionQueue) -> StdResult<()> {
+    singleton(storage, KEY_REDEMPTION_QUEUE).save(queue)
+}
+
+pub fn read_redemption_queue(storage: &dyn Storage) -> StdResult<RedemptionQueue> {
+    Ok(singleton_read(storage, KEY_REDEMPTION_QUEUE)
+        .may_load()?
+        .unwrap_or_default())
+}
+
+pub fn store_redemption_ticket(
+    storage: &mut dyn Storage,
+    ticket: &RedemptionTicket,
+) -> StdResult<()> {
+    bucket(storage, PREFIX_REDEMPTION_TICKETS).save(&ticket.id.to_be_bytes(), ticket)?;
+    Bucket::multilevel(storage, &[PREFIX_USER_TICKETS, ticket.owner.as_slice()])
+        .save(&ticket.id.to_be_bytes(), &ticket.id)
+}
+
+pub fn remove_redemption_ticket(storage: &mut dyn Storage, ticket: &RedemptionTicket) {
+    bucket::<RedemptionTicket>(storage, PREFIX_REDEMPTION_TICKETS).remove(&ticket.id.to_be_bytes());
+    Bucket::<u64>::multilevel(storage, &[PREFIX_USER_TICKETS, ticket.owner.as_slice()])
+        .remove(&ticket.id.to_be_bytes())
+}
+
+pub fn read_redemption_ticket(storage: &dyn Storage, id: u64) -> StdResult<RedemptionTicket> {
+    bucket_read(storage, PREFIX_REDEMPTION_TICKETS).load(&id.to_be_bytes())
+}
+
+/// Tickets from the given id on, in queue order.
+pub fn read_redemption_tickets_from(
+    storage: &dyn Storage,
+    start: u64,
+    limit: usize,
+) -> StdResult<Vec<RedemptionTicket>> {
+    bucket_read::<RedemptionTicket>(storage, PREFIX_REDEMPTION_TICKETS)
+        .range(Some(&start.to_be_bytes()), None, Order::Ascending)
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
+
+/// Unfilled tickets up to and including the given one, its 1-based position in the queue.
+pub fn read_queue_position(storage: &dyn Storage, head: u64, id: u64) -> StdResult<u64> {
+    Ok(
+        bucket_read::<RedemptionTicket>(storage, PREFIX_REDEMPTION_TICKETS)
+            .range(
+                Some(&head.to_be_bytes()),
+                Some(&(id + 1).to_be_bytes()),
+                Order::Ascending,
+            )
+            .count() as u64,
+    )
+}
+
+pub fn read_user_tickets(
+    storage: &dyn Storage,
+    owner: &CanonicalAddr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<RedemptionTicket>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| (id + 1).to_be_bytes());
+
+    ReadonlyBucket::<u64>::multilevel(storage, &[PREFIX_USER_TICKETS, owner.as_slice()])
+        .range(start.as_ref().map(|s| s.as_slice()), None, Order::Ascending)
+        .take(limit)
+        .map(|item| read_redemption_ticket(storage, item?.1))
+        .collect()
+}
+
+pub fn store_strategy(storage: &mut dyn Storage, strategy: Option<Strategy>) -> StdResult<()> {
+    match strategy {
+        Some(strategy) => singleton(storage, KEY_STRATEGY).save(&strategy),
+        None => {
+            singleton::<Strategy>(storage, KEY_STRATEGY).remove();
+            Ok(())
+        }
+    }
+}
+
+pub fn read_strategy(storage: &dyn Storage) -> StdResult<Option<Strategy>> {
+    singleton_read(storage, KEY_STRATEGY).may_load()
+}
+
+pub fn store_strategy_principal(storage: &mut dyn Storage, principal: Uint128) -> StdResult<()> {
+    singleton(storage, KEY_STRATEGY_PRINCIPAL).save(&principal)
+}
+
+/// reserves allocated to the strategy and not withdrawn yet
+pub fn read_strategy_principal(storage: &dyn Storage) -> StdResult<Uint128> {
+    Ok(singleton_read(storage, KEY_STRATEGY_PRINCIPAL)
+        .may_load()?
+        .unwrap_or_default())
+}
+
+pub fn store_backing(storage: &mut dyn Storage, backing: Uint128) -> StdResult<()> {
+    singleton(storage, KEY_BACKING).save(&backing)
+}
+
+/// native amount deposited for outstanding basset, independent of the bank balance
+pub fn read_backing(storage: &dyn Storage) -> StdResult<Uint128> {
+    Ok(singleton_read(storage, KEY_BACKING)
+        .may_load()?
+        .unwrap_or_default())
+}
+
+pub fn store_rounding_deficit(storage: &mut dyn Storage, deficit: Uint128) -> StdResult<()> {
+    singleton(storage, KEY_ROUNDING_DEFICIT).save(&deficit)
+}
+
+/// native value conversions rounded in the user's favor gave beyond their input
+pub fn read_rounding_deficit(storage: &dyn Storage) -> StdResult<Uint128> {
+    Ok(singleton_read(storage, KEY_ROUNDING_DEFICIT)
+        .may_load()?
+        .unwrap_or_default())
+}
+
+pub fn store_council(storage: &mut dyn Storage, council: Option<CouncilConfig>) -> StdResult<()> {
+    match council {
+        Some(council) => singleton(storage, KEY_COUNCIL).save(&council),
+        None => {
+            singleton::<CouncilConfig>(storage, KEY_COUNCIL).remove();
+            Ok(())
+        }
+    }
+}
+
+pub fn read_council(storage: &dyn Storage) -> StdResult<Option<CouncilConfig>> {
+    singleton_read(storage, KEY_COUNCIL).may_load()
+}
+
+pub fn store_council_epoch(storage: &mut dyn Storage, epoch: u64) -> StdResult<()> {
+    singleton(storage, KEY_COUNCIL_EPOCH).save(&epoch)
+}
+
+/// number of times the council was set or removed
+pub fn read_council_epoch(storage: &dyn Storage) -> StdResult<u64> {
+    Ok(singleton_read(storage, KEY_COUNCIL_EPOCH)
+        .may_load()?
+        .unwrap_or_default())
+}
+
+/// Marks the proposal whose action is being executed, for the admin log of that action.
+pub fn store_executing_proposal(storage: &mut dyn Storage, id: Option<u64>) -> StdResult<()> {
+    match id {
+        Some(id) => singleton(storage, KEY_EXECUTING_PROPOSAL).save(&id),
+        None => {
+            singleton::<u64>(storage, KEY_EXECUTING_PROPOSAL).remove();
+            Ok(())
+        }
+    }
+}
+
+pub fn read_executing_proposal(storage: &dyn Storage) -> StdResult<Option<u64>> {
+    singleton_read(storage, KEY_EXECUTING_PROPOSAL).may_load()
+}
+
+/// Stores a new proposal under the next id and returns it.
+pub fn create_proposal(storage: &mut dyn Storage, mut proposal: Proposal) -> StdResult<Proposal> {
+    let id: u64 = singleton_read(storage, KEY_PROPOSAL_COUNT)
+        .may_load()?
+        .unwrap_or_default();
+    singleton(storage, KEY_PROPOSAL_COUNT).save(&(id + 1))?;
+    proposal.id = id;
+    store_proposal(storage, &proposal)?;
+    Ok(proposal)
+}
+
+pub fn store_proposal(storage: &mut dyn Storage, proposal: &Proposal) -> StdResult<()> {
+    bucket(storage, PREFIX_PROPOSALS).save(&proposal.id.to_be_bytes(), proposal)
+}
+
+pub fn read_proposal(storage: &dyn Storage, id: u64) -> StdResult<Proposal> {
+    bucket_read(storage, PREFIX_PROPOSALS).load(&id.to_be_bytes())
+}
+
+pub fn read_proposals(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Proposal>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| (id + 1).to_be_bytes());
+
+    bucket_read::<Proposal>(storage, PREFIX_PROPOSALS)
+        .range(start.as_ref().map(|s| s.as_slice()), None, Order::Ascending)
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
+
+/// Appends an entry to the admin log under the next id.
+pub fn append_admin_log(storage: &mut dyn Storage, mut entry: AdminLogEntry) -> StdResult<()> {
+    let id: u64 = singleton_read(storage, KEY_ADMIN_LOG_COUNT)
+        .may_load()?
+        .unwrap_or_default();
+    singleton(storage, KEY_ADMIN_LOG_COUNT).save(&(id + 1))?;
+    entry.id = id;
+    bucket(storage, PREFIX_ADMIN_LOG).save(&id.to_be_bytes(), &entry)
+}
+
+pub fn read_admin_log(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<AdminLogEntry>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| (id + 1).to_be_bytes());
+
+    bucket_read::<AdminLogEntry>(storage, PREFIX_ADMIN_LOG)
+        .range(start.as_ref().map(|s| s.as_slice()), None, Order::Ascending)
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
+
+/// Converter of an equivalent pair, keyed by its basset token.
+pub fn store_equivalent_pair(
+    storage: &mut dyn Storage,
+    basset_token: &CanonicalAddr,
+    converter: &CanonicalAddr,
+) -> StdResult<()> {
+    bucket(storage, PREFIX_EQUIVALENT_PAIRS).save(basset_token.as_slice(), converter)
+}
+
+pub fn remove_equivalent_pair(storage: &mut dyn Storage, basset_token: &CanonicalAddr) {
+    bucket::<CanonicalAddr>(storage, PREFIX_EQUIVALENT_PAIRS).remove(basset_token.as_slice())
+}
+
+pub fn read_equivalent_pair(
+    storage: &dyn Storage,
+    basset_token: &CanonicalAddr,
+) -> StdResult<Option<CanonicalAddr>> {
+    bucket_read(storage, PREFIX_EQUIVALENT_PAIRS).may_load(basset_token.as_slice())
+}
+
+pub fn is_equivalent_converter(
+    storage: &dyn Storage,
+    converter: &CanonicalAddr,
+) -> StdResult<bool> {
+    for item in bucket_read::<CanonicalAddr>(storage, PREFIX_EQUIVALENT_PAIRS).range(
+        None,
+        None,
+        Order::Ascending,
+    ) {
+        if &item?.1 == converter {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+pub fn read_equivalent_pairs(
+    storage: &dyn Storage,
+    api: &dyn Api,
+    start_after: Option<CanonicalAddr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<EquivalentPair>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = calc_range_start(start_after);
+
+    bucket_read::<CanonicalAddr>(storage, PREFIX_EQUIVALENT_PAIRS)
+        .range(start.as_deref(), None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, converter) = item?;
+            Ok(EquivalentPair {
+                basset_token: api.addr_humanize(&CanonicalAddr::from(k))?.to_string(),
+                converter: api.addr_humanize(&converter)?.to_string(),
+            })
+        })
+        .collect()
+}
+
+pub fn store_role_member(
+    storage: &mut dyn Storage,
+    role: Role,
+    address: &CanonicalAddr,
+) -> StdResult<()> {
+    Bucket::multilevel(storage, &[PREFIX_ROLES, role.as_str().as_bytes()])
+        .save(address.as_slice(), &true)
+}
+
+pub fn remove_role_member(storage: &mut dyn Storage, role: Role, address: &CanonicalAddr) {
+    Bucket::<bool>::multilevel(storage, &[PREFIX_ROLES, role.as_str().as_bytes()])
+        .remove(address.as_slice())
+}
+
+pub fn is_role_member(
+    storage: &dyn Storage,
+    role: Role,
+    address: &CanonicalAddr,
+) -> StdResult<bool> {
+    Ok(
+        ReadonlyBucket::<bool>::multilevel(storage, &[PREFIX_ROLES, role.as_str().as_bytes()])
+            .may_load(address.as_slice())?
+            .is_some(),
+    )
+}
+
+pub fn read_role_members(
+    storage: &dyn Storage,
+    api: &dyn Api,
+    role: Role,
+) -> StdResult<Vec<String>> {
+    ReadonlyBucket::<bool>::multilevel(storage, &[PREFIX_ROLES, role.as_str().as_bytes()])
+        .range(None, None, Order::Ascending)
+        .map(|item| {
+            let (k, _) = item?;
+            Ok(api.addr_humanize(&CanonicalAddr::from(k))?.to_string())
+        })
+        .collect()
+}
+
+// this will set the first key after the provided key, by appending a 0 byte
+fn calc_range_start(start_after: Option<CanonicalAddr>) -> Option<Vec<u8>> {
+    start_after.map(|addr| {
+        let mut v = addr.as_slice().to_vec();
+        v.push(0);
+        v
+    })
+}
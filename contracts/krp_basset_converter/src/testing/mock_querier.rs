@@ -1,19 +1,31 @@
 use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage};
 use cosmwasm_std::{
-    from_json, to_json_binary, Coin, ContractResult, OwnedDeps, Querier, QuerierResult,
-    QueryRequest, SystemError, SystemResult, WasmQuery, Empty,
+    from_json, to_json_binary, BalanceResponse as BankBalanceResponse, BankMsg, BankQuery, Coin,
+    ContractResult, CosmosMsg, Empty, OwnedDeps, Querier, QuerierResult, QueryRequest, StdResult,
+    SupplyResponse, SystemError, SystemResult, Uint128, WasmQuery,
 };
 
-use cw20::TokenInfoResponse;
+use basset::converter::{
+    ConfigResponse, ConversionMode, OraclePriceResponse, RoundingMode, StrategyTotalValueResponse,
+};
+use basset::mock_tokenfactory::MockTokenFactory;
+use basset::tokenfactory::TokenFactoryMsg;
+use cosmwasm_bignumber::Decimal256;
+use cw20::{BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
 
 pub const MOCK_CONTRACT_ADDR: &str = "cosmos2contract";
+pub const MOCK_ORACLE_ADDR: &str = "oracle0000";
+pub const MOCK_STRATEGY_ADDR: &str = "strategy0000";
+pub const MOCK_PEER_CONVERTER_ADDR: &str = "peer_converter0000";
+pub const MOCK_PEER_BASSET_ADDR: &str = "peer_basset0000";
+pub const MOCK_UNDERLYING_TOKEN_ADDR: &str = "underlying0000";
 
 pub fn mock_dependencies(
     contract_balance: &[Coin],
 ) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
     let contract_addr = String::from(MOCK_CONTRACT_ADDR);
-    let custom_querier: WasmMockQuerier = WasmMockQuerier::new(
-        MockQuerier::new(&[(&contract_addr, contract_balance)]));
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(&contract_addr, contract_balance)]));
 
     OwnedDeps {
         storage: MockStorage::default(),
@@ -27,6 +39,18 @@ pub struct WasmMockQuerier {
     base: MockQuerier<Empty>,
     // first one is CW20 token decimals, the second one is native token decimals
     decimals: (u8, u8),
+    basset_total_supply: Uint128,
+    // rate and last update time of the oracle price
+    oracle_price: (Decimal256, u64),
+    strategy_value: Uint128,
+    // native denom of the equivalent pair's converter
+    peer_native_denom: String,
+    // converter balance of the cw20 underlying token
+    underlying_balance: Uint128,
+    // converter balance of the basset token
+    basset_balance: Uint128,
+    // supplies and balances of tokenfactory denoms
+    tokenfactory: MockTokenFactory,
 }
 
 impl Querier for WasmMockQuerier {
@@ -48,11 +72,48 @@ impl Querier for WasmMockQuerier {
 impl WasmMockQuerier {
     pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
         match &request {
-            QueryRequest::Wasm(WasmQuery::Smart {
-                contract_addr,
-                msg: _,
-            }) => {
-                if contract_addr == "native_token0000" {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                if contract_addr == MOCK_UNDERLYING_TOKEN_ADDR {
+                    match from_json(msg).unwrap() {
+                        Cw20QueryMsg::Balance { address: _ } => SystemResult::Ok(
+                            ContractResult::from(to_json_binary(&BalanceResponse {
+                                balance: self.underlying_balance,
+                            })),
+                        ),
+                        _ => SystemResult::Ok(ContractResult::from(to_json_binary(
+                            &TokenInfoResponse {
+                                name: "underlying_token".to_string(),
+                                symbol: "UNDER".to_string(),
+                                decimals: self.decimals.1,
+                                total_supply: Default::default(),
+                            },
+                        ))),
+                    }
+                } else if contract_addr == MOCK_STRATEGY_ADDR {
+                    SystemResult::Ok(ContractResult::from(to_json_binary(
+                        &StrategyTotalValueResponse {
+                            total_value: self.strategy_value,
+                        },
+                    )))
+                } else if contract_addr == MOCK_ORACLE_ADDR {
+                    SystemResult::Ok(ContractResult::from(to_json_binary(&OraclePriceResponse {
+                        rate: self.oracle_price.0,
+                        last_updated_base: self.oracle_price.1,
+                        last_updated_quote: self.oracle_price.1,
+                    })))
+                } else if contract_addr == MOCK_PEER_CONVERTER_ADDR {
+                    SystemResult::Ok(ContractResult::from(to_json_binary(&ConfigResponse {
+                        owner: "owner0000".to_string(),
+                        native_denom: Some(self.peer_native_denom.clone()),
+                        basset_token_address: Some(MOCK_PEER_BASSET_ADDR.to_string()),
+                        custody_contract: None,
+                        paused: false,
+                        rounding_mode: RoundingMode::Floor,
+                        mode: ConversionMode::Both,
+                        cw20_underlying: false,
+                        basset_denom: None,
+                    })))
+                } else if contract_addr == "native_token0000" {
                     SystemResult::Ok(ContractResult::from(to_json_binary(&TokenInfoResponse {
                         name: "native_token".to_string(),
                         symbol: "DENOM".to_string(),
@@ -60,14 +121,41 @@ impl WasmMockQuerier {
                         total_supply: Default::default(),
                     })))
                 } else {
-                    SystemResult::Ok(ContractResult::from(to_json_binary(&TokenInfoResponse {
-                        name: "basset_token".to_string(),
-                        symbol: "CW2O".to_string(),
-                        decimals: self.decimals.0,
-                        total_supply: Default::default(),
-                    })))
+                    match from_json(msg) {
+                        Ok(Cw20QueryMsg::Balance { address: _ }) => SystemResult::Ok(
+                            ContractResult::from(to_json_binary(&BalanceResponse {
+                                balance: self.basset_balance,
+                            })),
+                        ),
+                        _ => SystemResult::Ok(ContractResult::from(to_json_binary(
+                            &TokenInfoResponse {
+                                name: "basset_token".to_string(),
+                                symbol: "CW2O".to_string(),
+                                decimals: self.decimals.0,
+                                total_supply: self.basset_total_supply,
+                            },
+                        ))),
+                    }
                 }
             }
+            QueryRequest::Bank(BankQuery::Supply { denom }) if is_tokenfactory_denom(denom) => {
+                SystemResult::Ok(ContractResult::from(to_json_binary(&SupplyResponse::new(
+                    Coin {
+                        denom: denom.to_string(),
+                        amount: self.tokenfactory.supply(denom),
+                    },
+                ))))
+            }
+            QueryRequest::Bank(BankQuery::Balance { address, denom })
+                if is_tokenfactory_denom(denom) =>
+            {
+                SystemResult::Ok(ContractResult::from(to_json_binary(
+                    &BankBalanceResponse::new(Coin {
+                        denom: denom.to_string(),
+                        amount: self.tokenfactory.balance(address, denom),
+                    }),
+                )))
+            }
             _ => self.base.handle_query(request),
         }
     }
@@ -78,10 +166,67 @@ impl WasmMockQuerier {
         WasmMockQuerier {
             base,
             decimals: (6, 8),
+            basset_total_supply: Uint128::zero(),
+            oracle_price: (Decimal256::one(), 0),
+            strategy_value: Uint128::zero(),
+            peer_native_denom: "native_token0000".to_string(),
+            underlying_balance: Uint128::zero(),
+            basset_balance: Uint128::zero(),
+            tokenfactory: MockTokenFactory::default(),
         }
     }
 
     pub fn set_decimals(&mut self, basset_token_decimals: u8, native_decimals: u8) {
         self.decimals = (basset_token_decimals, native_decimals)
     }
+
+    pub fn set_basset_total_supply(&mut self, total_supply: Uint128) {
+        self.basset_total_supply = total_supply
+    }
+
+    pub fn update_balance(&mut self, balance: Vec<Coin>) {
+        self.base.update_balance(MOCK_CONTRACT_ADDR, balance);
+    }
+
+    pub fn set_strategy_value(&mut self, total_value: Uint128) {
+        self.strategy_value = total_value
+    }
+
+    pub fn set_oracle_price(&mut self, rate: Decimal256, last_updated: u64) {
+        self.oracle_price = (rate, last_updated)
+    }
+
+    pub fn set_peer_native_denom(&mut self, native_denom: &str) {
+        self.peer_native_denom = native_denom.to_string()
+    }
+
+    pub fn set_underlying_balance(&mut self, balance: Uint128) {
+        self.underlying_balance = balance
+    }
+
+    pub fn set_basset_balance(&mut self, balance: Uint128) {
+        self.basset_balance = balance
+    }
+
+    /// Applies a message sent by `sender` to the tokenfactory balances, other messages do
+    /// not move tokenfactory coins and are ignored.
+    pub fn execute_tokenfactory(
+        &mut self,
+        sender: &str,
+        msg: &CosmosMsg<TokenFactoryMsg>,
+    ) -> StdResult<()> {
+        match msg {
+            CosmosMsg::Custom(_) => self.tokenfactory.execute(sender, msg),
+            CosmosMsg::Bank(BankMsg::Send { amount, .. })
+                if amount.iter().all(|coin| is_tokenfactory_denom(&coin.denom)) =>
+            {
+                self.tokenfactory.execute(sender, msg)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn is_tokenfactory_denom(denom: &str) -> bool {
+    denom.starts_with("factory/")
 }
@@ -1,14 +1,34 @@
-use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
 use cosmwasm_std::{
-    from_json, to_json_binary, Attribute, BankMsg, Coin, CosmosMsg, StdError, SubMsg, Uint128,
+    from_json, to_json_binary, Attribute, BankMsg, Binary, Coin, CosmosMsg, Event, OwnedDeps,
+    Reply, ReplyOn, Response, StdError, StdResult, SubMsg, SubMsgResponse, SubMsgResult, Uint128,
     WasmMsg,
 };
 
-use crate::contract::{execute, instantiate, query};
-use crate::testing::mock_querier::mock_dependencies;
+use crate::contract::{
+    execute, instantiate, migrate, query, reply, sudo, CONVERT_FOR_RECIPIENT_REPLY_ID,
+    HARVEST_REPLY_ID, IBC_TRANSFER_REPLY_ID, VERIFY_SUPPLY_REPLY_ID,
+};
+use crate::ibc::transfer_with_callback;
+use crate::testing::mock_querier::{
+    mock_dependencies, WasmMockQuerier, MOCK_CONTRACT_ADDR, MOCK_ORACLE_ADDR,
+    MOCK_PEER_BASSET_ADDR, MOCK_PEER_CONVERTER_ADDR, MOCK_STRATEGY_ADDR,
+    MOCK_UNDERLYING_TOKEN_ADDR,
+};
 use basset::converter::Cw20HookMsg::ConvertBassetToNative;
 use basset::converter::ExecuteMsg::{self, Receive, RegisterTokens};
-use basset::converter::{ConfigResponse, InstantiateMsg, QueryMsg};
+use basset::converter::{
+    AccessMode, AccessModeResponse, AddressListResponse, AdminLogResponse, ConfigResponse,
+    ConversionMode, Council, CouncilResponse, CustodyCw20HookMsg, Cw20HookMsg, DenomOrCw20,
+    EquivalentPair, EquivalentPairsResponse, ExchangeRateResponse, IbcLifecycleComplete,
+    IbcTransferInfo, InstantiateMsg, InvariantsResponse, MigrateMsg, PriceFeed, ProposalResponse,
+    ProposalStatus, ProposalsResponse, QueryMsg, RedemptionTicketResponse,
+    RedemptionTicketsResponse, Role, RoleMembers, RolesResponse, RoundingMode, SimulationResponse,
+    StrategyConfig, StrategyExecuteMsg, StrategyResponse, SudoMsg, CONVERT_EVENT,
+    CONVERT_SENDER_KEY,
+};
+use basset::tokenfactory::{tokenfactory_denom, TokenFactoryMsg};
+use cosmwasm_bignumber::Decimal256;
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 
 const MOCK_OWNER_ADDR: &str = "owner0000";
@@ -39,6 +59,12 @@ fn proper_init() {
             owner: MOCK_OWNER_ADDR.to_string(),
             native_denom: None,
             basset_token_address: None,
+            custody_contract: None,
+            paused: false,
+            rounding_mode: RoundingMode::Floor,
+            mode: ConversionMode::Both,
+            cw20_underlying: false,
+            basset_denom: None,
         }
     );
 }
@@ -75,7 +101,11 @@ fn proper_convert_to_basset() {
         Attribute::new("action", "register_token_contracts")
     );
 
-    let msg = ExecuteMsg::ConvertNativeToBasset {};
+    let msg = ExecuteMsg::ConvertNativeToBasset {
+        recipient: None,
+        min_output: None,
+        deadline: None,
+    };
     // unauthorized request
     // Native conversion of basset does not require permission, this test case does not require it
     // let invalid_info = mock_info("invalid", &[Coin::new(100000000u128, MOCK_NATIVE_CONTRACT_ADDR)]);
@@ -92,16 +122,19 @@ fn proper_convert_to_basset() {
     assert_eq!(res.messages.len(), 1);
     assert_eq!(
         res.messages[0],
-        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
-            msg: to_json_binary(&Cw20ExecuteMsg::Mint {
-                recipient: sender.to_string(),
-                // 100000000 / 10^2 = 1000000
-                amount: Uint128::new(1000000)
-            })
-            .unwrap(),
-            funds: vec![]
-        }))
+        SubMsg::reply_on_success(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: sender.to_string(),
+                    // 100000000 / 10^2 = 1000000
+                    amount: Uint128::new(1000000)
+                })
+                .unwrap(),
+                funds: vec![]
+            }),
+            VERIFY_SUPPLY_REPLY_ID
+        )
     );
 
     //cannot convert less than 100 micro native
@@ -130,7 +163,7 @@ fn proper_convert_to_basset() {
 
 #[test]
 fn proper_convert_to_native() {
-    let mut deps = mock_dependencies(&[]);
+    let mut deps = mock_dependencies(&[Coin::new(1000000000000u128, MOCK_NATIVE_CONTRACT_ADDR)]);
     let init_msg = default_init();
 
     let sender = "addr0000";
@@ -160,10 +193,19 @@ fn proper_convert_to_native() {
         Attribute::new("action", "register_token_contracts")
     );
 
+    // the burned basset is part of the supply
+    deps.querier
+        .set_basset_total_supply(Uint128::new(100000000));
+
     let receive_msg = Receive(Cw20ReceiveMsg {
         sender: sender.to_string(),
         amount: Uint128::new(100000000),
-        msg: to_json_binary(&ConvertBassetToNative {}).unwrap(),
+        msg: to_json_binary(&ConvertBassetToNative {
+            ibc: None,
+            min_output: None,
+            deadline: None,
+        })
+        .unwrap(),
     });
 
     // unauthorized request
@@ -189,14 +231,17 @@ fn proper_convert_to_native() {
     );
     assert_eq!(
         res.messages[1],
-        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
-            msg: to_json_binary(&Cw20ExecuteMsg::Burn {
-                amount: Uint128::new(100000000)
-            })
-            .unwrap(),
-            funds: vec![]
-        }))
+        SubMsg::reply_on_success(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Burn {
+                    amount: Uint128::new(100000000)
+                })
+                .unwrap(),
+                funds: vec![]
+            }),
+            VERIFY_SUPPLY_REPLY_ID
+        )
     );
 }
 
@@ -244,7 +289,11 @@ fn proper_convert_to_basset_with_more_decimals() {
     //     execute(deps.as_mut(), mock_env(), invalid_info, receive_msg.clone()).unwrap_err();
     // assert_eq!(error_res, StdError::generic_err("unauthorized"));
 
-    let msg = ExecuteMsg::ConvertNativeToBasset {};
+    let msg = ExecuteMsg::ConvertNativeToBasset {
+        recipient: None,
+        min_output: None,
+        deadline: None,
+    };
     // successful request
     let native_info = mock_info(
         sender,
@@ -254,23 +303,26 @@ fn proper_convert_to_basset_with_more_decimals() {
     assert_eq!(res.messages.len(), 1);
     assert_eq!(
         res.messages[0],
-        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
-            msg: to_json_binary(&Cw20ExecuteMsg::Mint {
-                recipient: sender.to_string(),
-                //basset decimals is bigger than native then we should multiply with 10^2
-                // 100000000 * 10^2 = 10000000000
-                amount: Uint128::new(10000000000)
-            })
-            .unwrap(),
-            funds: vec![]
-        }))
+        SubMsg::reply_on_success(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: sender.to_string(),
+                    //basset decimals is bigger than native then we should multiply with 10^2
+                    // 100000000 * 10^2 = 10000000000
+                    amount: Uint128::new(10000000000)
+                })
+                .unwrap(),
+                funds: vec![]
+            }),
+            VERIFY_SUPPLY_REPLY_ID
+        )
     );
 }
 
 #[test]
 fn proper_convert_to_native_with_less_decimals() {
-    let mut deps = mock_dependencies(&[]);
+    let mut deps = mock_dependencies(&[Coin::new(1000000000000u128, MOCK_NATIVE_CONTRACT_ADDR)]);
     let init_msg = default_init();
 
     let sender = "addr0000";
@@ -300,10 +352,18 @@ fn proper_convert_to_native_with_less_decimals() {
         Attribute::new("action", "register_token_contracts")
     );
 
+    deps.querier
+        .set_basset_total_supply(Uint128::new(100000000));
+
     let receive_msg = Receive(Cw20ReceiveMsg {
         sender: sender.to_string(),
         amount: Uint128::new(100000000),
-        msg: to_json_binary(&ConvertBassetToNative {}).unwrap(),
+        msg: to_json_binary(&ConvertBassetToNative {
+            ibc: None,
+            min_output: None,
+            deadline: None,
+        })
+        .unwrap(),
     });
 
     // unauthorized request
@@ -330,21 +390,29 @@ fn proper_convert_to_native_with_less_decimals() {
     );
     assert_eq!(
         res.messages[1],
-        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
-            msg: to_json_binary(&Cw20ExecuteMsg::Burn {
-                amount: Uint128::new(100000000)
-            })
-            .unwrap(),
-            funds: vec![]
-        }))
+        SubMsg::reply_on_success(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Burn {
+                    amount: Uint128::new(100000000)
+                })
+                .unwrap(),
+                funds: vec![]
+            }),
+            VERIFY_SUPPLY_REPLY_ID
+        )
     );
 
     //cannot convert less than 100 micro native
     let receive_msg = Receive(Cw20ReceiveMsg {
         sender: sender.to_string(),
         amount: Uint128::new(1),
-        msg: to_json_binary(&ConvertBassetToNative {}).unwrap(),
+        msg: to_json_binary(&ConvertBassetToNative {
+            ibc: None,
+            min_output: None,
+            deadline: None,
+        })
+        .unwrap(),
     });
 
     // successful request
@@ -385,6 +453,23 @@ fn proper_update_config() {
     .unwrap_err();
     assert_eq!(error_res, StdError::generic_err("unauthorized"));
 
+    // decimals the conversion cannot scale are rejected
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 19,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("unsupported decimals; basset 6 and native 19 must not exceed 18")
+    );
+
     //successful one
     let res = execute(deps.as_mut(), mock_env(), info, update_config).unwrap();
     assert_eq!(
@@ -400,6 +485,4110 @@ fn proper_update_config() {
             owner: MOCK_OWNER_ADDR.to_string(),
             basset_token_address: Some("cw20_token0000".to_string()),
             native_denom: Some("native_token0000".to_string()),
+            custody_contract: None,
+            paused: false,
+            rounding_mode: RoundingMode::Floor,
+            mode: ConversionMode::Both,
+            cw20_underlying: false,
+            basset_denom: None,
         }
     );
 }
+
+#[test]
+fn proper_access_lists() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+
+    let allowed = "allowed0000";
+    let denied = "denied0000";
+    let owner_info = mock_info(MOCK_OWNER_ADDR, &[]);
+
+    instantiate(deps.as_mut(), mock_env(), owner_info.clone(), init_msg).unwrap();
+    deps.querier.set_decimals(6, 8);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 8,
+        },
+    )
+    .unwrap();
+
+    // default mode is open
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::AccessMode {}).unwrap();
+    let mode_response: AccessModeResponse = from_json(&res).unwrap();
+    assert_eq!(mode_response.mode, AccessMode::Open);
+
+    // only the owner can manage the lists and the mode
+    let update_allowlist = ExecuteMsg::UpdateAllowlist {
+        add: vec![allowed.to_string()],
+        remove: vec![],
+    };
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("invalid", &[]),
+        update_allowlist.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("invalid", &[]),
+        ExecuteMsg::UpdateAccessMode {
+            mode: AccessMode::Allowlist,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        update_allowlist,
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        ExecuteMsg::UpdateDenylist {
+            add: vec![denied.to_string()],
+            remove: vec![],
+        },
+    )
+    .unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Allowlist {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let list_response: AddressListResponse = from_json(&res).unwrap();
+    assert_eq!(list_response.addresses, vec![allowed.to_string()]);
+
+    // allowlist mode
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        ExecuteMsg::UpdateAccessMode {
+            mode: AccessMode::Allowlist,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[1], Attribute::new("mode", "allowlist"));
+
+    let msg = ExecuteMsg::ConvertNativeToBasset {
+        recipient: None,
+        min_output: None,
+        deadline: None,
+    };
+    let funds = [Coin::new(100000000u128, MOCK_NATIVE_CONTRACT_ADDR)];
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(allowed, &funds),
+        msg.clone(),
+    )
+    .unwrap();
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &funds),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("addr0000 is not on the allowlist")
+    );
+    // naming a listed recipient does not let an unlisted sender mint
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &funds),
+        ExecuteMsg::ConvertNativeToBasset {
+            recipient: Some(allowed.to_string()),
+            min_output: None,
+            deadline: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("addr0000 is not on the allowlist")
+    );
+
+    // redemptions are checked against the cw20 sender, not the token contract
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::new(100000000),
+        msg: to_json_binary(&ConvertBassetToNative {
+            ibc: None,
+            min_output: None,
+            deadline: None,
+        })
+        .unwrap(),
+    });
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_BASSET_TOKEN_CONTRACT_ADDR, &[]),
+        receive_msg,
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("addr0000 is not on the allowlist")
+    );
+
+    // denylist mode
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        ExecuteMsg::UpdateAccessMode {
+            mode: AccessMode::Denylist,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &funds),
+        msg.clone(),
+    )
+    .unwrap();
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: denied.to_string(),
+        amount: Uint128::new(100000000),
+        msg: to_json_binary(&ConvertBassetToNative {
+            ibc: None,
+            min_output: None,
+            deadline: None,
+        })
+        .unwrap(),
+    });
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_BASSET_TOKEN_CONTRACT_ADDR, &[]),
+        receive_msg,
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("denied0000 is on the denylist")
+    );
+
+    // removing from the denylist lifts the restriction
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info,
+        ExecuteMsg::UpdateDenylist {
+            add: vec![],
+            remove: vec![denied.to_string()],
+        },
+    )
+    .unwrap();
+    execute(deps.as_mut(), mock_env(), mock_info(denied, &funds), msg).unwrap();
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Denylist {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let list_response: AddressListResponse = from_json(&res).unwrap();
+    assert!(list_response.addresses.is_empty());
+}
+
+#[test]
+fn proper_roles() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+
+    let admin = "admin0000";
+    let operator = "operator0000";
+    let guardian = "guardian0000";
+    let owner_info = mock_info(MOCK_OWNER_ADDR, &[]);
+
+    instantiate(deps.as_mut(), mock_env(), owner_info.clone(), init_msg).unwrap();
+
+    // only admins can grant roles
+    let grant_admin = ExecuteMsg::GrantRole {
+        role: Role::Admin,
+        address: admin.to_string(),
+    };
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(operator, &[]),
+        grant_admin.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+
+    execute(deps.as_mut(), mock_env(), owner_info, grant_admin).unwrap();
+    let admin_info = mock_info(admin, &[]);
+    for (role, address) in [(Role::Operator, operator), (Role::Guardian, guardian)] {
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            admin_info.clone(),
+            ExecuteMsg::GrantRole {
+                role,
+                address: address.to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(res.attributes[1], Attribute::new("role", role.as_str()));
+    }
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Roles {}).unwrap();
+    let roles_response: RolesResponse = from_json(&res).unwrap();
+    assert_eq!(
+        roles_response.roles,
+        vec![
+            RoleMembers {
+                role: Role::Admin,
+                addresses: vec![admin.to_string()],
+            },
+            RoleMembers {
+                role: Role::Operator,
+                addresses: vec![operator.to_string()],
+            },
+            RoleMembers {
+                role: Role::Guardian,
+                addresses: vec![guardian.to_string()],
+            },
+            RoleMembers {
+                role: Role::Treasurer,
+                addresses: vec![],
+            },
+        ]
+    );
+
+    // the operator manages access lists but cannot register tokens
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(operator, &[]),
+        ExecuteMsg::UpdateAccessMode {
+            mode: AccessMode::Denylist,
+        },
+    )
+    .unwrap();
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(operator, &[]),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 8,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+
+    // the guardian can pause but not resume
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(guardian, &[]),
+        ExecuteMsg::Pause {},
+    )
+    .unwrap();
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(guardian, &[]),
+        ExecuteMsg::Unpause {},
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            "addr0000",
+            &[Coin::new(100000000u128, MOCK_NATIVE_CONTRACT_ADDR)],
+        ),
+        ExecuteMsg::ConvertNativeToBasset {
+            recipient: None,
+            min_output: None,
+            deadline: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("conversions are paused"));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        admin_info.clone(),
+        ExecuteMsg::Unpause {},
+    )
+    .unwrap();
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config_response: ConfigResponse = from_json(&res).unwrap();
+    assert!(!config_response.paused);
+
+    // revoked roles lose their permissions
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        admin_info,
+        ExecuteMsg::RevokeRole {
+            role: Role::Guardian,
+            address: guardian.to_string(),
+        },
+    )
+    .unwrap();
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(guardian, &[]),
+        ExecuteMsg::Pause {},
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+}
+
+#[test]
+fn proper_sweep_surplus() {
+    // 1000 native units of surplus on top of the backing of 1 basset
+    let mut deps = mock_dependencies(&[Coin::new(100001000u128, MOCK_NATIVE_CONTRACT_ADDR)]);
+    let init_msg = default_init();
+    let owner_info = mock_info(MOCK_OWNER_ADDR, &[]);
+    let treasurer = "treasurer0000";
+
+    instantiate(deps.as_mut(), mock_env(), owner_info.clone(), init_msg).unwrap();
+    deps.querier.set_decimals(6, 8);
+    deps.querier.set_basset_total_supply(Uint128::new(1000000));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 8,
+        },
+    )
+    .unwrap();
+
+    let sweep_msg = ExecuteMsg::SweepSurplus {
+        recipient: treasurer.to_string(),
+    };
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(treasurer, &[]),
+        sweep_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info,
+        ExecuteMsg::GrantRole {
+            role: Role::Treasurer,
+            address: treasurer.to_string(),
+        },
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(treasurer, &[]),
+        sweep_msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: treasurer.to_string(),
+            amount: vec![Coin::new(1000u128, MOCK_NATIVE_CONTRACT_ADDR)],
+        }))]
+    );
+
+    // backing funds are never swept
+    deps.querier.set_basset_total_supply(Uint128::new(1000010));
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(treasurer, &[]),
+        sweep_msg,
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("no surplus reserves to sweep")
+    );
+}
+
+#[test]
+fn proper_sweep_surplus_keeps_backing() {
+    let mut deps = mock_dependencies(&[]);
+    let owner_info = mock_info(MOCK_OWNER_ADDR, &[]);
+    let treasurer = "treasurer0000";
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        default_init(),
+    )
+    .unwrap();
+    deps.querier.set_decimals(6, 8);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 8,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info,
+        ExecuteMsg::GrantRole {
+            role: Role::Treasurer,
+            address: treasurer.to_string(),
+        },
+    )
+    .unwrap();
+
+    // the 50 native units of dust the mint truncates are credited to the backing
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            "addr0000",
+            &[Coin::new(100000050u128, MOCK_NATIVE_CONTRACT_ADDR)],
+        ),
+        ExecuteMsg::ConvertNativeToBasset {
+            recipient: None,
+            min_output: None,
+            deadline: None,
+        },
+    )
+    .unwrap();
+    deps.querier.set_basset_total_supply(Uint128::new(1000000));
+    deps.querier
+        .update_balance(vec![Coin::new(100001050u128, MOCK_NATIVE_CONTRACT_ADDR)]);
+
+    // only the 1000 units beyond the backing are swept
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(treasurer, &[]),
+        ExecuteMsg::SweepSurplus {
+            recipient: treasurer.to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: treasurer.to_string(),
+            amount: vec![Coin::new(1000u128, MOCK_NATIVE_CONTRACT_ADDR)],
+        }))]
+    );
+    deps.querier
+        .update_balance(vec![Coin::new(100000050u128, MOCK_NATIVE_CONTRACT_ADDR)]);
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Invariants {}).unwrap();
+    let invariants: InvariantsResponse = from_json(&res).unwrap();
+    assert_eq!(invariants.backing, Uint128::new(100000050));
+    assert_eq!(invariants.unaccounted_reserves, Uint128::zero());
+    assert_eq!(invariants.missing_reserves, Uint128::zero());
+    assert_eq!(invariants.unbacked_supply, Uint128::zero());
+}
+
+#[test]
+fn proper_recover_tokens() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+    let owner_info = mock_info(MOCK_OWNER_ADDR, &[]);
+    let user = "addr0000";
+
+    instantiate(deps.as_mut(), mock_env(), owner_info.clone(), init_msg).unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 8,
+        },
+    )
+    .unwrap();
+
+    // unauthorized request
+    let recover_msg = ExecuteMsg::RecoverTokens {
+        denom_or_cw20: DenomOrCw20::Native("uother".to_string()),
+        amount: Uint128::new(100),
+        recipient: Some(user.to_string()),
+    };
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(user, &[]),
+        recover_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+
+    // unrelated native denom
+    let res = execute(deps.as_mut(), mock_env(), owner_info.clone(), recover_msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: user.to_string(),
+            amount: vec![Coin::new(100u128, "uother")],
+        }))]
+    );
+
+    // the reserve denom cannot be recovered
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        ExecuteMsg::RecoverTokens {
+            denom_or_cw20: DenomOrCw20::Native(MOCK_NATIVE_CONTRACT_ADDR.to_string()),
+            amount: Uint128::new(100),
+            recipient: Some(user.to_string()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err(
+            "the registered native denom backs the basset supply and cannot be recovered"
+        )
+    );
+
+    // basset received by plain transfer is returned
+    deps.querier.set_basset_balance(Uint128::new(200));
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        ExecuteMsg::RecoverTokens {
+            denom_or_cw20: DenomOrCw20::Cw20(MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string()),
+            amount: Uint128::new(100),
+            recipient: Some(user.to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: user.to_string(),
+                amount: Uint128::new(100),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    deps.querier.set_basset_total_supply(Uint128::new(100));
+    // or burned
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        ExecuteMsg::RecoverTokens {
+            denom_or_cw20: DenomOrCw20::Cw20(MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string()),
+            amount: Uint128::new(100),
+            recipient: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_on_success(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Burn {
+                    amount: Uint128::new(100),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            VERIFY_SUPPLY_REPLY_ID
+        )]
+    );
+
+    // other cw20 tokens cannot be burned
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info,
+        ExecuteMsg::RecoverTokens {
+            denom_or_cw20: DenomOrCw20::Cw20("other_token0000".to_string()),
+            amount: Uint128::new(100),
+            recipient: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("recipient is required for cw20 tokens other than basset")
+    );
+
+    let res: AdminLogResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::AdminLog {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    let entry = res.entries.last().unwrap();
+    assert_eq!(
+        (
+            entry.action.as_str(),
+            entry.old_value.as_str(),
+            entry.new_value.as_str()
+        ),
+        (
+            "recover_tokens",
+            "{\"cw20\":\"cw20_token0000\"}",
+            "[\"100\",\"burn\"]"
+        )
+    );
+}
+
+#[test]
+fn proper_convert_to_native_over_ibc() {
+    let mut deps = mock_dependencies(&[Coin::new(1000000000000u128, MOCK_NATIVE_CONTRACT_ADDR)]);
+    let init_msg = default_init();
+    let sender = "addr0000";
+
+    instantiate(deps.as_mut(), mock_env(), mock_info(sender, &[]), init_msg).unwrap();
+    deps.querier.set_decimals(6, 8);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 8,
+        },
+    )
+    .unwrap();
+
+    deps.querier
+        .set_basset_total_supply(Uint128::new(100000000));
+
+    let ibc = IbcTransferInfo {
+        channel_id: "channel-0".to_string(),
+        receiver: "noble1receiver".to_string(),
+        timeout_seconds: 600,
+    };
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(100000000),
+        msg: to_json_binary(&ConvertBassetToNative {
+            ibc: Some(ibc.clone()),
+            min_output: None,
+            deadline: None,
+        })
+        .unwrap(),
+    });
+
+    let env = mock_env();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MOCK_BASSET_TOKEN_CONTRACT_ADDR, &[]),
+        receive_msg,
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 2);
+    // same decimal conversion as a bank send, 100000000 * 10^2
+    let returned = Coin::new(10000000000u128, MOCK_NATIVE_CONTRACT_ADDR);
+    assert_eq!(
+        res.messages[0],
+        SubMsg::reply_on_success(
+            transfer_with_callback(
+                "channel-0",
+                MOCK_CONTRACT_ADDR,
+                "noble1receiver",
+                &returned,
+                env.block.time.plus_seconds(600),
+                MOCK_CONTRACT_ADDR,
+            ),
+            IBC_TRANSFER_REPLY_ID
+        )
+    );
+    assert_eq!(
+        res.messages[1],
+        SubMsg::reply_on_success(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Burn {
+                    amount: Uint128::new(100000000)
+                })
+                .unwrap(),
+                funds: vec![]
+            }),
+            VERIFY_SUPPLY_REPLY_ID
+        )
+    );
+    assert_eq!(
+        res.attributes[1],
+        Attribute::new("recipient", "noble1receiver")
+    );
+
+    // the transfer reply keys the escrow by the packet sequence, MsgTransferResponse { 7 }
+    let transfer_reply = |sequence: u8| Reply {
+        id: IBC_TRANSFER_REPLY_ID,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: Some(Binary::from(vec![0x08, sequence])),
+        }),
+    };
+    reply(deps.as_mut(), env.clone(), transfer_reply(7)).unwrap();
+
+    // a timed out transfer credits the sender with the refunded native amount
+    let timeout = |sequence: u64| {
+        SudoMsg::IbcLifecycleComplete(IbcLifecycleComplete::IbcTimeout {
+            channel: "channel-0".to_string(),
+            sequence,
+        })
+    };
+    let error_res = sudo(deps.as_mut(), env.clone(), timeout(8)).unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("no ibc redemption is pending for sequence 8 on channel-0")
+    );
+    let res = sudo(deps.as_mut(), env.clone(), timeout(7)).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: sender.to_string(),
+            amount: vec![returned.clone()],
+        }))]
+    );
+    // the escrow is released once
+    assert!(sudo(deps.as_mut(), env.clone(), timeout(7)).is_err());
+
+    // an acknowledged transfer releases the escrow without a refund
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MOCK_BASSET_TOKEN_CONTRACT_ADDR, &[]),
+        Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::new(100000000),
+            msg: to_json_binary(&ConvertBassetToNative {
+                ibc: Some(ibc.clone()),
+                min_output: None,
+                deadline: None,
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap();
+    reply(deps.as_mut(), env.clone(), transfer_reply(8)).unwrap();
+    let res = sudo(
+        deps.as_mut(),
+        env,
+        SudoMsg::IbcLifecycleComplete(IbcLifecycleComplete::IbcAck {
+            channel: "channel-0".to_string(),
+            sequence: 8,
+            ack: "eyJyZXN1bHQiOiJBUT09In0=".to_string(),
+            success: true,
+        }),
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+
+    // zero timeout is rejected
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(100000000),
+        msg: to_json_binary(&ConvertBassetToNative {
+            ibc: Some(IbcTransferInfo {
+                timeout_seconds: 0,
+                ..ibc
+            }),
+            min_output: None,
+            deadline: None,
+        })
+        .unwrap(),
+    });
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_BASSET_TOKEN_CONTRACT_ADDR, &[]),
+        receive_msg,
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("ibc timeout must be greater than zero")
+    );
+}
+
+#[test]
+fn proper_convert_for_recipient() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+    // ibc-hooks executes the memo from an address derived from the channel and the sender
+    let intermediate_sender = "ibc_hooks_intermediary0000";
+    let recipient = "recipient0000";
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        init_msg,
+    )
+    .unwrap();
+    deps.querier.set_decimals(6, 8);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 8,
+        },
+    )
+    .unwrap();
+
+    let env = mock_env();
+    let funds = vec![Coin::new(100000000u128, MOCK_NATIVE_CONTRACT_ADDR)];
+    let msg = ExecuteMsg::ConvertNativeToBasset {
+        recipient: Some(recipient.to_string()),
+        min_output: None,
+        deadline: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(intermediate_sender, &funds),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_always(
+            WasmMsg::Execute {
+                contract_addr: env.contract.address.to_string(),
+                msg: to_json_binary(&msg).unwrap(),
+                funds: funds.clone(),
+            },
+            CONVERT_FOR_RECIPIENT_REPLY_ID,
+        )]
+    );
+
+    // the converter mints to the recipient, never to the intermediate sender
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(env.contract.address.as_str(), &funds),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_on_success(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: recipient.to_string(),
+                    amount: Uint128::new(1000000),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            VERIFY_SUPPLY_REPLY_ID
+        )]
+    );
+    // the conversion is reported with the sender of the first leg
+    assert!(res.events[0]
+        .attributes
+        .contains(&Attribute::new(CONVERT_SENDER_KEY, intermediate_sender)));
+
+    // a successful conversion needs nothing else
+    let res = reply(
+        deps.as_mut(),
+        env.clone(),
+        Reply {
+            id: CONVERT_FOR_RECIPIENT_REPLY_ID,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+
+    // a failed conversion pays the native funds to the recipient
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(intermediate_sender, &funds),
+        msg,
+    )
+    .unwrap();
+    let res = reply(
+        deps.as_mut(),
+        env,
+        Reply {
+            id: CONVERT_FOR_RECIPIENT_REPLY_ID,
+            result: SubMsgResult::Err("conversions are paused".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: funds,
+        }))]
+    );
+    assert_eq!(
+        res.attributes[2],
+        Attribute::new("error", "conversions are paused")
+    );
+}
+
+#[test]
+fn proper_convert_and_deposit_collateral() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+    let sender = "addr0000";
+    let custody = "custody0000";
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        init_msg,
+    )
+    .unwrap();
+    deps.querier.set_decimals(6, 8);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 8,
+        },
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::ConvertAndDepositCollateral {
+        custody: custody.to_string(),
+    };
+    let funds = [Coin::new(100000000u128, MOCK_NATIVE_CONTRACT_ADDR)];
+
+    // custody must be registered first
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &funds),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("custody contract does not match the registered custody")
+    );
+
+    // only admins can set the custody
+    let set_custody = ExecuteMsg::SetCustody {
+        custody: custody.to_string(),
+    };
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        set_custody.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        set_custody,
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config_response: ConfigResponse = from_json(&res).unwrap();
+    assert_eq!(config_response.custody_contract, Some(custody.to_string()));
+
+    // a different custody is rejected
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &funds),
+        ExecuteMsg::ConvertAndDepositCollateral {
+            custody: "custody0001".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("custody contract does not match the registered custody")
+    );
+
+    let env = mock_env();
+    let res = execute(deps.as_mut(), env.clone(), mock_info(sender, &funds), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::reply_on_success(
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+                    msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+                        recipient: env.contract.address.to_string(),
+                        amount: Uint128::new(1000000),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }),
+                VERIFY_SUPPLY_REPLY_ID
+            ),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                    contract: custody.to_string(),
+                    amount: Uint128::new(1000000),
+                    msg: to_json_binary(&CustodyCw20HookMsg::DepositCollateral {
+                        borrower: Some(sender.to_string()),
+                    })
+                    .unwrap(),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+        ]
+    );
+}
+
+#[test]
+fn proper_exchange_rate() {
+    let mut deps = mock_dependencies(&[Coin::new(1000000000000u128, MOCK_NATIVE_CONTRACT_ADDR)]);
+    let init_msg = default_init();
+    let sender = "addr0000";
+    let oracle = "oracle0000";
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        init_msg,
+    )
+    .unwrap();
+    deps.querier.set_decimals(6, 8);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 8,
+        },
+    )
+    .unwrap();
+
+    // parity by default
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::ExchangeRate {}).unwrap();
+    let rate_response: ExchangeRateResponse = from_json(&res).unwrap();
+    assert_eq!(
+        rate_response,
+        ExchangeRateResponse {
+            exchange_rate: Decimal256::one(),
+            last_updated: None,
+            oracle: None,
+            price_feed: None,
+        }
+    );
+
+    let update_rate = ExecuteMsg::UpdateExchangeRate {
+        exchange_rate: Decimal256::from_ratio(3u64, 2u64),
+    };
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(oracle, &[]),
+        update_rate.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::SetRateOracle {
+            oracle: Some(oracle.to_string()),
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(oracle, &[]),
+        update_rate,
+    )
+    .unwrap();
+
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(oracle, &[]),
+        ExecuteMsg::UpdateExchangeRate {
+            exchange_rate: Decimal256::zero(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("exchange rate must be greater than zero")
+    );
+
+    // 100000001 native / 10^2 = 1000000, * 1.5 = 1500000 basset
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            sender,
+            &[Coin::new(100000001u128, MOCK_NATIVE_CONTRACT_ADDR)],
+        ),
+        ExecuteMsg::ConvertNativeToBasset {
+            recipient: None,
+            min_output: None,
+            deadline: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::reply_on_success(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: sender.to_string(),
+                    amount: Uint128::new(1500000),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            VERIFY_SUPPLY_REPLY_ID
+        )
+    );
+
+    deps.querier.set_basset_total_supply(Uint128::new(1500000));
+    // 1000001 basset / 1.5 = 666667.33 rounded down, * 10^2
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(1000001),
+        msg: to_json_binary(&ConvertBassetToNative {
+            ibc: None,
+            min_output: None,
+            deadline: None,
+        })
+        .unwrap(),
+    });
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_BASSET_TOKEN_CONTRACT_ADDR, &[]),
+        receive_msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: sender.to_string(),
+            amount: vec![Coin::new(66666700u128, MOCK_NATIVE_CONTRACT_ADDR)],
+        }))
+    );
+}
+
+#[test]
+fn proper_price_feed() {
+    let mut deps = mock_dependencies(&[Coin::new(1000000000000u128, MOCK_NATIVE_CONTRACT_ADDR)]);
+    let sender = "addr0000";
+    let env = mock_env();
+    let now = env.block.time.seconds();
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        default_init(),
+    )
+    .unwrap();
+    deps.querier.set_decimals(6, 6);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 6,
+        },
+    )
+    .unwrap();
+
+    let price_feed = PriceFeed {
+        oracle: MOCK_ORACLE_ADDR.to_string(),
+        base: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+        quote: "uusd".to_string(),
+        max_age: 60,
+        max_deviation: Decimal256::percent(10),
+    };
+    let set_price_feed = ExecuteMsg::SetPriceFeed {
+        price_feed: Some(price_feed.clone()),
+    };
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        set_price_feed.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        set_price_feed,
+    )
+    .unwrap();
+
+    let convert = ExecuteMsg::ConvertNativeToBasset {
+        recipient: None,
+        min_output: None,
+        deadline: None,
+    };
+    let funds = [Coin::new(1000000u128, MOCK_NATIVE_CONTRACT_ADDR)];
+
+    // stale price
+    deps.querier
+        .set_oracle_price(Decimal256::percent(105), now - 61);
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &funds),
+        convert.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err(format!(
+            "oracle price is stale, last updated at {}",
+            now - 61
+        ))
+    );
+
+    // too far from the last accepted rate of 1
+    deps.querier.set_oracle_price(Decimal256::percent(111), now);
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &funds),
+        convert.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("oracle price 1.11 deviates too much from the last exchange rate 1")
+    );
+
+    deps.querier
+        .set_oracle_price(Decimal256::percent(105), now - 60);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &funds),
+        convert,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::reply_on_success(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: sender.to_string(),
+                    amount: Uint128::new(1050000),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            VERIFY_SUPPLY_REPLY_ID
+        )
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::ExchangeRate {}).unwrap();
+    let rate_response: ExchangeRateResponse = from_json(&res).unwrap();
+    assert_eq!(
+        rate_response,
+        ExchangeRateResponse {
+            exchange_rate: Decimal256::percent(105),
+            last_updated: Some(now - 60),
+            oracle: None,
+            price_feed: Some(price_feed),
+        }
+    );
+
+    // redemptions use the oracle price as well
+    deps.querier.set_basset_total_supply(Uint128::new(1100000));
+    deps.querier.set_oracle_price(Decimal256::percent(110), now);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_BASSET_TOKEN_CONTRACT_ADDR, &[]),
+        Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::new(1100000),
+            msg: to_json_binary(&ConvertBassetToNative {
+                ibc: None,
+                min_output: None,
+                deadline: None,
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: sender.to_string(),
+            amount: vec![Coin::new(1000000u128, MOCK_NATIVE_CONTRACT_ADDR)],
+        }))
+    );
+}
+
+#[test]
+fn proper_rounding_mode() {
+    let mut deps = mock_dependencies(&[]);
+    let sender = "addr0000";
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        default_init(),
+    )
+    .unwrap();
+    deps.querier.set_decimals(6, 8);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 8,
+        },
+    )
+    .unwrap();
+
+    // 100000050 native / 10^2 = 1000000.5, rounded down in the converter's favor
+    let simulate = QueryMsg::SimulateConvertToBasset {
+        amount: Uint128::new(100000050),
+    };
+    let res = query(deps.as_ref(), mock_env(), simulate.clone()).unwrap();
+    let simulation: SimulationResponse = from_json(&res).unwrap();
+    assert_eq!(
+        simulation,
+        SimulationResponse {
+            amount: Uint128::new(1000000),
+            rounding_mode: RoundingMode::Floor,
+        }
+    );
+
+    let update_mode = ExecuteMsg::UpdateRoundingMode {
+        rounding_mode: RoundingMode::Ceil,
+    };
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        update_mode.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        update_mode,
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), simulate).unwrap();
+    let simulation: SimulationResponse = from_json(&res).unwrap();
+    assert_eq!(simulation.amount, Uint128::new(1000001));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            sender,
+            &[Coin::new(100000050u128, MOCK_NATIVE_CONTRACT_ADDR)],
+        ),
+        ExecuteMsg::ConvertNativeToBasset {
+            recipient: None,
+            min_output: None,
+            deadline: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "convert-to-basset"),
+            Attribute::new("recipient", sender),
+            Attribute::new("minted_amount", "1000001"),
+            Attribute::new("rounding_mode", "ceil"),
+        ]
+    );
+    // the native value rounded up is tolerated by the backing check instead of pausing
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Invariants {}).unwrap();
+    let invariants: InvariantsResponse = from_json(&res).unwrap();
+    assert_eq!(invariants.rounding_deficit, Uint128::new(50));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::UpdateRoundingMode {
+            rounding_mode: RoundingMode::HalfEven,
+        },
+    )
+    .unwrap();
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SimulateConvertToNative {
+            amount: Uint128::new(1000000),
+        },
+    )
+    .unwrap();
+    let simulation: SimulationResponse = from_json(&res).unwrap();
+    assert_eq!(
+        simulation,
+        SimulationResponse {
+            amount: Uint128::new(100000000),
+            rounding_mode: RoundingMode::HalfEven,
+        }
+    );
+}
+
+#[test]
+fn proper_redemption_queue() {
+    let mut deps = mock_dependencies(&[Coin::new(100u128, MOCK_NATIVE_CONTRACT_ADDR)]);
+    let first = "addr0000";
+    let second = "addr0001";
+    let third = "addr0002";
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        default_init(),
+    )
+    .unwrap();
+    deps.querier.set_decimals(6, 6);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 6,
+        },
+    )
+    .unwrap();
+
+    let redeem = |sender: &str, amount: u128| {
+        Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::new(amount),
+            msg: to_json_binary(&ConvertBassetToNative {
+                ibc: None,
+                min_output: None,
+                deadline: None,
+            })
+            .unwrap(),
+        })
+    };
+
+    // more than the reserves, the basset is escrowed
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_BASSET_TOKEN_CONTRACT_ADDR, &[]),
+        redeem(first, 150),
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+    // nothing is converted yet
+    assert!(res.events.is_empty());
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "queue_redemption"),
+            Attribute::new("ticket_id", "0"),
+            Attribute::new("owner", first),
+            Attribute::new("basset_amount", "150"),
+            Attribute::new("native_amount", "150"),
+        ]
+    );
+
+    // the reserves would cover it, but an earlier ticket is waiting
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_BASSET_TOKEN_CONTRACT_ADDR, &[]),
+        redeem(second, 50),
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::RedemptionTickets {
+            owner: second.to_string(),
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let tickets: RedemptionTicketsResponse = from_json(&res).unwrap();
+    assert_eq!(
+        tickets.tickets,
+        vec![RedemptionTicketResponse {
+            id: 1,
+            owner: second.to_string(),
+            basset_amount: Uint128::new(50),
+            native_amount: Uint128::new(50),
+            filled: false,
+            queue_position: Some(2),
+        }]
+    );
+
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(first, &[]),
+        ExecuteMsg::ClaimRedemption { id: 0 },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("redemption ticket 0 is not filled yet")
+    );
+
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(first, &[]),
+        ExecuteMsg::CancelRedemption { id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(second, &[]),
+        ExecuteMsg::CancelRedemption { id: 1 },
+    )
+    .unwrap();
+    assert!(res.events.is_empty());
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: second.to_string(),
+                amount: Uint128::new(50),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    // escrowed basset cannot be recovered
+    deps.querier.set_basset_balance(Uint128::new(150));
+    let recover = |amount: u128| ExecuteMsg::RecoverTokens {
+        denom_or_cw20: DenomOrCw20::Cw20(MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string()),
+        amount: Uint128::new(amount),
+        recipient: Some(MOCK_OWNER_ADDR.to_string()),
+    };
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        recover(150),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err(
+            "only 0 basset is recoverable, 150 is escrowed for queued redemptions"
+        )
+    );
+    deps.querier.set_basset_balance(Uint128::new(160));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        recover(10),
+    )
+    .unwrap();
+
+    // reserves arrive, the first ticket is filled and its reserves are held for it
+    deps.querier
+        .update_balance(vec![Coin::new(200u128, MOCK_NATIVE_CONTRACT_ADDR)]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_BASSET_TOKEN_CONTRACT_ADDR, &[]),
+        redeem(third, 100),
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::RedemptionTicket { id: 2 },
+    )
+    .unwrap();
+    let ticket: RedemptionTicketResponse = from_json(&res).unwrap();
+    assert_eq!(ticket.queue_position, Some(1));
+
+    deps.querier.set_basset_total_supply(Uint128::new(150));
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(first, &[]),
+        ExecuteMsg::ClaimRedemption { id: 0 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.events,
+        vec![Event::new(CONVERT_EVENT).add_attributes(vec![
+            ("direction", "to_native"),
+            (
+                "pair",
+                &format!(
+                    "{}:{}",
+                    MOCK_NATIVE_CONTRACT_ADDR, MOCK_BASSET_TOKEN_CONTRACT_ADDR
+                ),
+            ),
+            ("sender", first),
+            ("recipient", first),
+            ("input_amount", "150"),
+            ("output_amount", "150"),
+            ("fee", "0"),
+            ("dust", "0"),
+        ])]
+    );
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: first.to_string(),
+                amount: vec![Coin::new(150u128, MOCK_NATIVE_CONTRACT_ADDR)],
+            })),
+            SubMsg::reply_on_success(
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+                    msg: to_json_binary(&Cw20ExecuteMsg::Burn {
+                        amount: Uint128::new(150),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }),
+                VERIFY_SUPPLY_REPLY_ID
+            ),
+        ]
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::RedemptionTickets {
+            owner: first.to_string(),
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let tickets: RedemptionTicketsResponse = from_json(&res).unwrap();
+    assert!(tickets.tickets.is_empty());
+}
+
+#[test]
+fn proper_strategy() {
+    let mut deps = mock_dependencies(&[Coin::new(1000u128, MOCK_NATIVE_CONTRACT_ADDR)]);
+    let treasurer = "treasurer0000";
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        default_init(),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 8,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::GrantRole {
+            role: Role::Treasurer,
+            address: treasurer.to_string(),
+        },
+    )
+    .unwrap();
+
+    let allocate = ExecuteMsg::AllocateToStrategy {
+        amount: Uint128::new(500),
+    };
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(treasurer, &[]),
+        allocate.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("no strategy is set"));
+
+    let strategy = StrategyConfig {
+        strategy: MOCK_STRATEGY_ADDR.to_string(),
+        max_allocation: Decimal256::percent(60),
+        liquidity_buffer: Uint128::new(450),
+    };
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(treasurer, &[]),
+        ExecuteMsg::SetStrategy {
+            strategy: Some(strategy.clone()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::SetStrategy {
+            strategy: Some(strategy.clone()),
+        },
+    )
+    .unwrap();
+
+    // capped at 60% of the reserves
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(treasurer, &[]),
+        ExecuteMsg::AllocateToStrategy {
+            amount: Uint128::new(601),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("allocation exceeds the strategy cap of 600")
+    );
+    // the buffer stays in the converter
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(treasurer, &[]),
+        ExecuteMsg::AllocateToStrategy {
+            amount: Uint128::new(551),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("allocation leaves less than 450 in the converter")
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(treasurer, &[]),
+        allocate,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_STRATEGY_ADDR.to_string(),
+            msg: to_json_binary(&StrategyExecuteMsg::Deposit {}).unwrap(),
+            funds: vec![Coin::new(500u128, MOCK_NATIVE_CONTRACT_ADDR)],
+        }))]
+    );
+
+    // the strategy earned 20, which is not part of the backing
+    deps.querier
+        .update_balance(vec![Coin::new(500u128, MOCK_NATIVE_CONTRACT_ADDR)]);
+    deps.querier.set_strategy_value(Uint128::new(520));
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Strategy {}).unwrap();
+    let strategy_response: StrategyResponse = from_json(&res).unwrap();
+    assert_eq!(
+        strategy_response,
+        StrategyResponse {
+            strategy: Some(strategy),
+            principal: Uint128::new(500),
+            total_value: Uint128::new(520),
+            accrued_yield: Uint128::new(20),
+        }
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(treasurer, &[]),
+        ExecuteMsg::HarvestStrategyYield {
+            recipient: treasurer.to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_on_success(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_STRATEGY_ADDR.to_string(),
+                msg: to_json_binary(&StrategyExecuteMsg::Withdraw {
+                    amount: Uint128::new(20),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            HARVEST_REPLY_ID
+        )]
+    );
+
+    // a strategy that returns only 10 of the yield gets only 10 paid out
+    deps.querier
+        .update_balance(vec![Coin::new(510u128, MOCK_NATIVE_CONTRACT_ADDR)]);
+    let harvest_reply = Reply {
+        id: HARVEST_REPLY_ID,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+    let res = reply(deps.as_mut(), mock_env(), harvest_reply.clone()).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: treasurer.to_string(),
+            amount: vec![Coin::new(10u128, MOCK_NATIVE_CONTRACT_ADDR)],
+        }))]
+    );
+
+    // and one that returns nothing fails the harvest
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(treasurer, &[]),
+        ExecuteMsg::HarvestStrategyYield {
+            recipient: treasurer.to_string(),
+        },
+    )
+    .unwrap();
+    let error_res = reply(deps.as_mut(), mock_env(), harvest_reply).unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("strategy returned no yield")
+    );
+    deps.querier
+        .update_balance(vec![Coin::new(500u128, MOCK_NATIVE_CONTRACT_ADDR)]);
+
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::SetStrategy { strategy: None },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("strategy still holds reserves, withdraw them first")
+    );
+
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(treasurer, &[]),
+        ExecuteMsg::WithdrawFromStrategy {
+            amount: Uint128::new(501),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("cannot withdraw more than the allocated 500")
+    );
+
+    // operators can pull reserves back, guardians only pause
+    for (role, address) in [
+        (Role::Guardian, "guardian0000"),
+        (Role::Operator, "operator0000"),
+    ] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MOCK_OWNER_ADDR, &[]),
+            ExecuteMsg::GrantRole {
+                role,
+                address: address.to_string(),
+            },
+        )
+        .unwrap();
+    }
+    let withdraw = ExecuteMsg::WithdrawFromStrategy {
+        amount: Uint128::new(250),
+    };
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian0000", &[]),
+        withdraw.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("operator0000", &[]),
+        withdraw.clone(),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(treasurer, &[]),
+        withdraw,
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::SetStrategy { strategy: None },
+    )
+    .unwrap();
+}
+
+#[test]
+fn proper_invariants() {
+    let mut deps = mock_dependencies(&[]);
+    let sender = "addr0000";
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        default_init(),
+    )
+    .unwrap();
+    deps.querier.set_decimals(6, 8);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 8,
+        },
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            sender,
+            &[Coin::new(100000000u128, MOCK_NATIVE_CONTRACT_ADDR)],
+        ),
+        ExecuteMsg::ConvertNativeToBasset {
+            recipient: None,
+            min_output: None,
+            deadline: None,
+        },
+    )
+    .unwrap();
+    // the deposit plus a donation
+    deps.querier
+        .update_balance(vec![Coin::new(100000005u128, MOCK_NATIVE_CONTRACT_ADDR)]);
+    deps.querier.set_basset_total_supply(Uint128::new(1000000));
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Invariants {}).unwrap();
+    let invariants: InvariantsResponse = from_json(&res).unwrap();
+    assert_eq!(
+        invariants,
+        InvariantsResponse {
+            backing: Uint128::new(100000000),
+            bank_balance: Uint128::new(100000005),
+            strategy_principal: Uint128::zero(),
+            basset_supply: Uint128::new(1000000),
+            required_backing: Uint128::new(100000000),
+            unaccounted_reserves: Uint128::new(5),
+            missing_reserves: Uint128::zero(),
+            unbacked_supply: Uint128::zero(),
+            rounding_deficit: Uint128::zero(),
+        }
+    );
+
+    // basset minted outside the converter
+    deps.querier.set_basset_total_supply(Uint128::new(1500000));
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Invariants {}).unwrap();
+    let invariants: InvariantsResponse = from_json(&res).unwrap();
+    assert_eq!(invariants.unbacked_supply, Uint128::new(50000000));
+
+    // redemptions reduce the backing by the returned amount
+    deps.querier.set_basset_total_supply(Uint128::new(1000000));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_BASSET_TOKEN_CONTRACT_ADDR, &[]),
+        Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::new(400000),
+            msg: to_json_binary(&ConvertBassetToNative {
+                ibc: None,
+                min_output: None,
+                deadline: None,
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap();
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Invariants {}).unwrap();
+    let invariants: InvariantsResponse = from_json(&res).unwrap();
+    assert_eq!(invariants.backing, Uint128::new(60000000));
+}
+
+#[test]
+fn proper_migrate_backing() {
+    let mut deps = mock_dependencies(&[]);
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        default_init(),
+    )
+    .unwrap();
+    deps.querier.set_decimals(6, 8);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 8,
+        },
+    )
+    .unwrap();
+
+    // supply minted before the ledger existed
+    deps.querier.set_basset_total_supply(Uint128::new(2000000));
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Invariants {}).unwrap();
+    let invariants: InvariantsResponse = from_json(&res).unwrap();
+    assert_eq!(invariants.backing, Uint128::new(200000000));
+    assert_eq!(invariants.unbacked_supply, Uint128::zero());
+}
+
+#[test]
+fn proper_auto_pause() {
+    let mut deps = mock_dependencies(&[Coin::new(100000000u128, MOCK_NATIVE_CONTRACT_ADDR)]);
+    let sender = "addr0000";
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        default_init(),
+    )
+    .unwrap();
+    deps.querier.set_decimals(6, 8);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 8,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            sender,
+            &[Coin::new(100000000u128, MOCK_NATIVE_CONTRACT_ADDR)],
+        ),
+        ExecuteMsg::ConvertNativeToBasset {
+            recipient: None,
+            min_output: None,
+            deadline: None,
+        },
+    )
+    .unwrap();
+
+    // a misconfigured minter minted basset outside the converter
+    deps.querier.set_basset_total_supply(Uint128::new(1500000));
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_BASSET_TOKEN_CONTRACT_ADDR, &[]),
+        Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::new(100000),
+            msg: to_json_binary(&ConvertBassetToNative {
+                ibc: None,
+                min_output: None,
+                deadline: None,
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: sender.to_string(),
+                amount: Uint128::new(100000),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+    assert_eq!(
+        res.events,
+        vec![Event::new("invariant_violation").add_attributes(vec![
+            ("invariant", "backing_covers_supply"),
+            ("backing", "90000000"),
+            ("required_backing", "140000000"),
+            ("basset_supply", "1400000"),
+        ])]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config_response: ConfigResponse = from_json(&res).unwrap();
+    assert!(config_response.paused);
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Invariants {}).unwrap();
+    let invariants: InvariantsResponse = from_json(&res).unwrap();
+    assert_eq!(invariants.backing, Uint128::new(100000000));
+
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            sender,
+            &[Coin::new(100000000u128, MOCK_NATIVE_CONTRACT_ADDR)],
+        ),
+        ExecuteMsg::ConvertNativeToBasset {
+            recipient: None,
+            min_output: None,
+            deadline: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("conversions are paused"));
+
+    // mints that would stay unbacked are refunded as well
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::Unpause {},
+    )
+    .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[Coin::new(1000000u128, MOCK_NATIVE_CONTRACT_ADDR)]),
+        ExecuteMsg::ConvertNativeToBasset {
+            recipient: None,
+            min_output: None,
+            deadline: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: sender.to_string(),
+            amount: vec![Coin::new(1000000u128, MOCK_NATIVE_CONTRACT_ADDR)],
+        }))]
+    );
+    assert_eq!(res.attributes, vec![Attribute::new("action", "auto_pause")]);
+}
+
+#[test]
+fn proper_council() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        default_init(),
+    )
+    .unwrap();
+
+    // threshold above the member count
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::SetCouncil {
+            council: Some(Council {
+                members: vec!["member0000".to_string(), "member0001".to_string()],
+                threshold: 3,
+                voting_period: 100,
+            }),
+        },
+    );
+    assert!(res.is_err());
+
+    let council = Council {
+        members: vec![
+            "member0000".to_string(),
+            "member0001".to_string(),
+            "member0002".to_string(),
+        ],
+        threshold: 2,
+        voting_period: 100,
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::SetCouncil {
+            council: Some(council.clone()),
+        },
+    )
+    .unwrap();
+    let res: CouncilResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Council {}).unwrap()).unwrap();
+    assert_eq!(res.council, Some(council));
+
+    // the owner lost direct admin access but keeps the other roles
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::Pause {},
+    );
+    assert!(res.is_ok());
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::Unpause {},
+    );
+    assert_eq!(res.unwrap_err(), StdError::generic_err("unauthorized"));
+
+    // only members propose and only role gated actions
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::Propose {
+            action: Box::new(ExecuteMsg::Unpause {}),
+        },
+    );
+    assert_eq!(res.unwrap_err(), StdError::generic_err("unauthorized"));
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("member0000", &[]),
+        ExecuteMsg::Propose {
+            action: Box::new(ExecuteMsg::ClaimRedemption { id: 0 }),
+        },
+    );
+    assert_eq!(
+        res.unwrap_err(),
+        StdError::generic_err("action cannot be proposed")
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("member0000", &[]),
+        ExecuteMsg::Propose {
+            action: Box::new(ExecuteMsg::Unpause {}),
+        },
+    )
+    .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("member0000", &[]),
+        ExecuteMsg::Execute { id: 0 },
+    );
+    assert_eq!(
+        res.unwrap_err(),
+        StdError::generic_err("proposal has not passed")
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("member0000", &[]),
+        ExecuteMsg::Approve { id: 0 },
+    );
+    assert_eq!(res.unwrap_err(), StdError::generic_err("already voted"));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("member0001", &[]),
+        ExecuteMsg::Approve { id: 0 },
+    )
+    .unwrap();
+
+    let res: ProposalResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Proposal { id: 0 }).unwrap()).unwrap();
+    assert_eq!(res.status, ProposalStatus::Passed);
+    assert_eq!(
+        res.approvals,
+        vec!["member0000".to_string(), "member0001".to_string()]
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("member0002", &[]),
+        ExecuteMsg::Execute { id: 0 },
+    )
+    .unwrap();
+    let res: ConfigResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert!(!res.paused);
+
+    // the admin log records the proposal and its approvers
+    let res: AdminLogResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::AdminLog {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    let entry = res.entries.last().unwrap();
+    assert_eq!(entry.action, "unpause");
+    assert_eq!(entry.sender, MOCK_CONTRACT_ADDR);
+    assert_eq!(entry.proposal_id, Some(0));
+    assert_eq!(
+        entry.approvers,
+        vec!["member0000".to_string(), "member0001".to_string()]
+    );
+    assert_eq!(res.entries[0].proposal_id, None);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("member0002", &[]),
+        ExecuteMsg::Execute { id: 0 },
+    );
+    assert_eq!(
+        res.unwrap_err(),
+        StdError::generic_err("proposal has not passed")
+    );
+
+    // two rejections make the threshold unreachable
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("member0000", &[]),
+        ExecuteMsg::Propose {
+            action: Box::new(ExecuteMsg::SetCouncil { council: None }),
+        },
+    )
+    .unwrap();
+    for member in ["member0001", "member0002"] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(member, &[]),
+            ExecuteMsg::Reject { id: 1 },
+        )
+        .unwrap();
+    }
+
+    // an unvoted proposal expires after the voting period
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("member0000", &[]),
+        ExecuteMsg::Propose {
+            action: Box::new(ExecuteMsg::SetCouncil { council: None }),
+        },
+    )
+    .unwrap();
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(100);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("member0001", &[]),
+        ExecuteMsg::Approve { id: 2 },
+    );
+    assert_eq!(
+        res.unwrap_err(),
+        StdError::generic_err("proposal is not open for voting")
+    );
+
+    let res: ProposalsResponse = from_json(
+        query(
+            deps.as_ref(),
+            env,
+            QueryMsg::Proposals {
+                start_after: Some(0),
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    let statuses: Vec<ProposalStatus> = res.proposals.iter().map(|p| p.status).collect();
+    assert_eq!(
+        statuses,
+        vec![ProposalStatus::Rejected, ProposalStatus::Expired]
+    );
+
+    // replacing the council voids the proposals of the previous one
+    for action in [ExecuteMsg::Pause {}, ExecuteMsg::Unpause {}] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("member0000", &[]),
+            ExecuteMsg::Propose {
+                action: Box::new(action),
+            },
+        )
+        .unwrap();
+    }
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("member0001", &[]),
+        ExecuteMsg::Approve { id: 3 },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("member0000", &[]),
+        ExecuteMsg::Propose {
+            action: Box::new(ExecuteMsg::SetCouncil {
+                council: Some(Council {
+                    members: vec!["member0000".to_string(), "member0001".to_string()],
+                    threshold: 2,
+                    voting_period: 100,
+                }),
+            }),
+        },
+    )
+    .unwrap();
+    for (member, msg) in [
+        ("member0001", ExecuteMsg::Approve { id: 5 }),
+        ("member0000", ExecuteMsg::Execute { id: 5 }),
+    ] {
+        execute(deps.as_mut(), mock_env(), mock_info(member, &[]), msg).unwrap();
+    }
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("member0000", &[]),
+        ExecuteMsg::Execute { id: 3 },
+    );
+    assert_eq!(
+        res.unwrap_err(),
+        StdError::generic_err("proposal was made under a replaced council")
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("member0001", &[]),
+        ExecuteMsg::Approve { id: 4 },
+    );
+    assert_eq!(
+        res.unwrap_err(),
+        StdError::generic_err("proposal was made under a replaced council")
+    );
+    let res: ProposalResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Proposal { id: 3 }).unwrap()).unwrap();
+    assert_eq!(res.status, ProposalStatus::Expired);
+}
+
+#[test]
+fn proper_admin_log() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        default_init(),
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::GrantRole {
+            role: Role::Guardian,
+            address: "guardian0000".to_string(),
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian0000", &[]),
+        ExecuteMsg::Pause {},
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::UpdateRoundingMode {
+            rounding_mode: RoundingMode::Ceil,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::SetOwner {
+            new_owner_addr: "owner0001".to_string(),
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0001", &[]),
+        ExecuteMsg::AcceptOwnership {},
+    )
+    .unwrap();
+
+    let res: AdminLogResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::AdminLog {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    let entries: Vec<(&str, &str, &str, &str)> = res
+        .entries
+        .iter()
+        .map(|entry| {
+            (
+                entry.sender.as_str(),
+                entry.action.as_str(),
+                entry.old_value.as_str(),
+                entry.new_value.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        entries,
+        vec![
+            (MOCK_OWNER_ADDR, "grant_role", "[]", "[\"guardian0000\"]"),
+            ("guardian0000", "pause", "false", "true"),
+            (
+                MOCK_OWNER_ADDR,
+                "update_rounding_mode",
+                "\"floor\"",
+                "\"ceil\""
+            ),
+            (
+                MOCK_OWNER_ADDR,
+                "set_owner",
+                "\"owner0000\"",
+                "\"owner0001\""
+            ),
+            (
+                "owner0001",
+                "accept_ownership",
+                "\"owner0000\"",
+                "\"owner0001\""
+            ),
+        ]
+    );
+    assert_eq!(res.entries[0].time, mock_env().block.time.seconds());
+
+    let res: AdminLogResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::AdminLog {
+                start_after: Some(2),
+                limit: Some(1),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.entries.len(), 1);
+    assert_eq!(res.entries[0].id, 3);
+
+    // reserves moved by treasurers are logged as well
+    let treasurer = "treasurer0000";
+    deps.querier
+        .update_balance(vec![Coin::new(1000u128, MOCK_NATIVE_CONTRACT_ADDR)]);
+    for msg in [
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 8,
+        },
+        ExecuteMsg::GrantRole {
+            role: Role::Treasurer,
+            address: treasurer.to_string(),
+        },
+        ExecuteMsg::SetStrategy {
+            strategy: Some(StrategyConfig {
+                strategy: MOCK_STRATEGY_ADDR.to_string(),
+                max_allocation: Decimal256::percent(60),
+                liquidity_buffer: Uint128::zero(),
+            }),
+        },
+    ] {
+        execute(deps.as_mut(), mock_env(), mock_info("owner0001", &[]), msg).unwrap();
+    }
+    for msg in [
+        ExecuteMsg::AllocateToStrategy {
+            amount: Uint128::new(500),
+        },
+        ExecuteMsg::SweepSurplus {
+            recipient: treasurer.to_string(),
+        },
+    ] {
+        execute(deps.as_mut(), mock_env(), mock_info(treasurer, &[]), msg).unwrap();
+    }
+
+    let res: AdminLogResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::AdminLog {
+                start_after: Some(4),
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    let entries: Vec<(&str, &str, &str, &str)> = res
+        .entries
+        .iter()
+        .filter(|entry| entry.sender == treasurer)
+        .map(|entry| {
+            (
+                entry.sender.as_str(),
+                entry.action.as_str(),
+                entry.old_value.as_str(),
+                entry.new_value.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        entries,
+        vec![
+            (treasurer, "allocate_to_strategy", "\"0\"", "\"500\""),
+            (
+                treasurer,
+                "sweep_surplus",
+                "{\"native\":\"native_token0000\"}",
+                "[\"1000\",\"treasurer0000\"]"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn proper_convert_events() {
+    let mut deps = mock_dependencies(&[Coin::new(100000001u128, MOCK_NATIVE_CONTRACT_ADDR)]);
+    let sender = "addr0000";
+    let pair = format!(
+        "{}:{}",
+        MOCK_NATIVE_CONTRACT_ADDR, MOCK_BASSET_TOKEN_CONTRACT_ADDR
+    );
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        default_init(),
+    )
+    .unwrap();
+    deps.querier.set_decimals(6, 8);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 8,
+        },
+    )
+    .unwrap();
+
+    // the last native unit is below one basset unit
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            sender,
+            &[Coin::new(100000001u128, MOCK_NATIVE_CONTRACT_ADDR)],
+        ),
+        ExecuteMsg::ConvertNativeToBasset {
+            recipient: None,
+            min_output: None,
+            deadline: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.events,
+        vec![Event::new(CONVERT_EVENT).add_attributes(vec![
+            ("direction", "to_basset"),
+            ("pair", &pair),
+            ("sender", sender),
+            ("recipient", sender),
+            ("input_amount", "100000001"),
+            ("output_amount", "1000000"),
+            ("fee", "0"),
+            ("dust", "1"),
+        ])]
+    );
+
+    deps.querier.set_basset_total_supply(Uint128::new(1000000));
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_BASSET_TOKEN_CONTRACT_ADDR, &[]),
+        Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::new(1000000),
+            msg: to_json_binary(&ConvertBassetToNative {
+                ibc: None,
+                min_output: None,
+                deadline: None,
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap();
+    assert_eq!(
+        res.events,
+        vec![Event::new(CONVERT_EVENT).add_attributes(vec![
+            ("direction", "to_native"),
+            ("pair", &pair),
+            ("sender", sender),
+            ("recipient", sender),
+            ("input_amount", "1000000"),
+            ("output_amount", "100000000"),
+            ("fee", "0"),
+            ("dust", "0"),
+        ])]
+    );
+}
+
+#[test]
+fn proper_verify_supply() {
+    let mut deps = mock_dependencies(&[Coin::new(100000000u128, MOCK_NATIVE_CONTRACT_ADDR)]);
+    let sender = "addr0000";
+    let supply_reply = || Reply {
+        id: VERIFY_SUPPLY_REPLY_ID,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        default_init(),
+    )
+    .unwrap();
+    deps.querier.set_decimals(6, 8);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 8,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            sender,
+            &[Coin::new(100000000u128, MOCK_NATIVE_CONTRACT_ADDR)],
+        ),
+        ExecuteMsg::ConvertNativeToBasset {
+            recipient: None,
+            min_output: None,
+            deadline: None,
+        },
+    )
+    .unwrap();
+
+    // the token contract did not mint
+    let error_res = reply(deps.as_mut(), mock_env(), supply_reply()).unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("basset supply is 0 but 1000000 was expected")
+    );
+
+    deps.querier.set_basset_total_supply(Uint128::new(1000000));
+    reply(deps.as_mut(), mock_env(), supply_reply()).unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_BASSET_TOKEN_CONTRACT_ADDR, &[]),
+        Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::new(1000000),
+            msg: to_json_binary(&ConvertBassetToNative {
+                ibc: None,
+                min_output: None,
+                deadline: None,
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap();
+
+    // a burn that moved the supply by a different amount
+    deps.querier.set_basset_total_supply(Uint128::new(100));
+    let error_res = reply(deps.as_mut(), mock_env(), supply_reply()).unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("basset supply is 100 but 0 was expected")
+    );
+    deps.querier.set_basset_total_supply(Uint128::zero());
+    reply(deps.as_mut(), mock_env(), supply_reply()).unwrap();
+}
+
+#[test]
+fn proper_conversion_mode() {
+    let mut deps = mock_dependencies(&[Coin::new(100000000u128, MOCK_NATIVE_CONTRACT_ADDR)]);
+    let sender = "addr0000";
+    let deposit = |amount: u128| mock_info(sender, &[Coin::new(amount, MOCK_NATIVE_CONTRACT_ADDR)]);
+    let redeem = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(500000),
+        msg: to_json_binary(&ConvertBassetToNative {
+            ibc: None,
+            min_output: None,
+            deadline: None,
+        })
+        .unwrap(),
+    });
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        default_init(),
+    )
+    .unwrap();
+    deps.querier.set_decimals(6, 8);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 8,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        deposit(100000000),
+        ExecuteMsg::ConvertNativeToBasset {
+            recipient: None,
+            min_output: None,
+            deadline: None,
+        },
+    )
+    .unwrap();
+    deps.querier.set_basset_total_supply(Uint128::new(1000000));
+
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::UpdateConversionMode {
+            mode: ConversionMode::RedeemOnly,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+
+    // sunsetting the pair keeps redemptions open
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::UpdateConversionMode {
+            mode: ConversionMode::RedeemOnly,
+        },
+    )
+    .unwrap();
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config_response: ConfigResponse = from_json(&res).unwrap();
+    assert_eq!(config_response.mode, ConversionMode::RedeemOnly);
+
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        deposit(100000000),
+        ExecuteMsg::ConvertNativeToBasset {
+            recipient: None,
+            min_output: None,
+            deadline: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("conversions to basset are disabled for this pair")
+    );
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_BASSET_TOKEN_CONTRACT_ADDR, &[]),
+        redeem.clone(),
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::UpdateConversionMode {
+            mode: ConversionMode::MintOnly,
+        },
+    )
+    .unwrap();
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_BASSET_TOKEN_CONTRACT_ADDR, &[]),
+        redeem.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("conversions to native are disabled for this pair")
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::UpdateConversionMode {
+            mode: ConversionMode::Disabled,
+        },
+    )
+    .unwrap();
+    assert!(execute(
+        deps.as_mut(),
+        mock_env(),
+        deposit(100000000),
+        ExecuteMsg::ConvertNativeToBasset {
+            recipient: None,
+            min_output: None,
+            deadline: None
+        },
+    )
+    .is_err());
+    assert!(execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_BASSET_TOKEN_CONTRACT_ADDR, &[]),
+        redeem,
+    )
+    .is_err());
+}
+
+#[test]
+fn proper_conversion_limits() {
+    let mut deps = mock_dependencies(&[Coin::new(100000000u128, MOCK_NATIVE_CONTRACT_ADDR)]);
+    let sender = "addr0000";
+    let now = mock_env().block.time;
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        default_init(),
+    )
+    .unwrap();
+    deps.querier.set_decimals(6, 8);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 8,
+        },
+    )
+    .unwrap();
+
+    let convert = |min_output: u128, deadline| ExecuteMsg::ConvertNativeToBasset {
+        recipient: None,
+        min_output: Some(Uint128::new(min_output)),
+        deadline: Some(deadline),
+    };
+    let deposit = mock_info(
+        sender,
+        &[Coin::new(100000000u128, MOCK_NATIVE_CONTRACT_ADDR)],
+    );
+
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        deposit.clone(),
+        convert(1000001, now),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("output 1000000 is below the minimum output 1000001")
+    );
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        deposit.clone(),
+        convert(1000000, now.minus_seconds(1)),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("conversion deadline has passed")
+    );
+
+    // a conversion for a recipient fails too instead of paying the funds to the recipient
+    let convert_for = |min_output: u128, deadline| ExecuteMsg::ConvertNativeToBasset {
+        recipient: Some("addr0001".to_string()),
+        min_output: Some(Uint128::new(min_output)),
+        deadline: Some(deadline),
+    };
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        deposit.clone(),
+        convert_for(1000001, now),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("output 1000000 is below the minimum output 1000001")
+    );
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        deposit.clone(),
+        convert_for(1000000, now.minus_seconds(1)),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("conversion deadline has passed")
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        deposit.clone(),
+        convert_for(1000000, now),
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+
+    execute(deps.as_mut(), mock_env(), deposit, convert(1000000, now)).unwrap();
+
+    deps.querier.set_basset_total_supply(Uint128::new(1000000));
+    let redeem = |min_output: u128, deadline| {
+        Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::new(1000000),
+            msg: to_json_binary(&ConvertBassetToNative {
+                ibc: None,
+                min_output: Some(Uint128::new(min_output)),
+                deadline: Some(deadline),
+            })
+            .unwrap(),
+        })
+    };
+    let basset_info = mock_info(MOCK_BASSET_TOKEN_CONTRACT_ADDR, &[]);
+
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        basset_info.clone(),
+        redeem(100000001, now),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("output 100000000 is below the minimum output 100000001")
+    );
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        basset_info.clone(),
+        redeem(100000000, now.minus_seconds(1)),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("conversion deadline has passed")
+    );
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        basset_info,
+        redeem(100000000, now),
+    )
+    .unwrap();
+}
+
+#[test]
+fn proper_batch_convert() {
+    let mut deps = mock_dependencies(&[Coin::new(300000000u128, MOCK_NATIVE_CONTRACT_ADDR)]);
+    let operator = "operator0000";
+    let batch = |amounts: &[u128]| ExecuteMsg::BatchConvertNativeToBasset {
+        recipients: amounts
+            .iter()
+            .enumerate()
+            .map(|(i, amount)| (format!("addr000{}", i), Uint128::new(*amount)))
+            .collect(),
+    };
+    let funds = |amount: u128| mock_info(operator, &[Coin::new(amount, MOCK_NATIVE_CONTRACT_ADDR)]);
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        default_init(),
+    )
+    .unwrap();
+    deps.querier.set_decimals(6, 8);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 8,
+        },
+    )
+    .unwrap();
+
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        funds(300000000),
+        batch(&[100000000, 200000000]),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::GrantRole {
+            role: Role::Operator,
+            address: operator.to_string(),
+        },
+    )
+    .unwrap();
+
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        funds(300000000),
+        batch(&[100000000, 100000000]),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("recipient amounts add up to 200000000 but 300000000 was sent")
+    );
+    let error_res = execute(deps.as_mut(), mock_env(), funds(51), batch(&[1; 51])).unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("a batch converts for 1 to 50 recipients")
+    );
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        funds(100000001),
+        batch(&[100000000, 1]),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err(
+            "cannot convert; conversion is only possible for amounts greater than 100 native token"
+        )
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        funds(300000000),
+        batch(&[100000000, 200000000]),
+    )
+    .unwrap();
+    let mint = |recipient: &str, amount: u128| {
+        SubMsg::reply_on_success(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: recipient.to_string(),
+                    amount: Uint128::new(amount),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            VERIFY_SUPPLY_REPLY_ID,
+        )
+    };
+    assert_eq!(
+        res.messages,
+        vec![mint("addr0000", 1000000), mint("addr0001", 2000000)]
+    );
+    assert_eq!(res.events.len(), 2);
+
+    // each mint is checked against the supply the mints before it leave
+    let supply_reply = || Reply {
+        id: VERIFY_SUPPLY_REPLY_ID,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+    deps.querier.set_basset_total_supply(Uint128::new(1000000));
+    reply(deps.as_mut(), mock_env(), supply_reply()).unwrap();
+    let error_res = reply(deps.as_mut(), mock_env(), supply_reply()).unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("basset supply is 1000000 but 3000000 was expected")
+    );
+    deps.querier.set_basset_total_supply(Uint128::new(3000000));
+    reply(deps.as_mut(), mock_env(), supply_reply()).unwrap();
+}
+
+#[test]
+fn proper_swap_basset() {
+    let mut deps = mock_dependencies(&[Coin::new(1000000000000u128, MOCK_NATIVE_CONTRACT_ADDR)]);
+    let sender = "addr0000";
+    let set_pair = |basset_token: &str, converter: Option<&str>| ExecuteMsg::SetEquivalentPair {
+        basset_token: basset_token.to_string(),
+        converter: converter.map(|c| c.to_string()),
+    };
+    let swap = |amount: u128| {
+        Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::new(amount),
+            msg: to_json_binary(&Cw20HookMsg::SwapBasset {
+                target_basset: MOCK_PEER_BASSET_ADDR.to_string(),
+                min_output: None,
+                deadline: None,
+            })
+            .unwrap(),
+        })
+    };
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        default_init(),
+    )
+    .unwrap();
+    deps.querier.set_decimals(6, 8);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 8,
+        },
+    )
+    .unwrap();
+    deps.querier
+        .set_basset_total_supply(Uint128::new(100000000));
+
+    let basset_info = mock_info(MOCK_BASSET_TOKEN_CONTRACT_ADDR, &[]);
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        basset_info.clone(),
+        swap(100000000),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("peer_basset0000 is not registered as an equivalent basset")
+    );
+
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        set_pair(MOCK_PEER_BASSET_ADDR, Some(MOCK_PEER_CONVERTER_ADDR)),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        set_pair("other_basset0000", Some(MOCK_PEER_CONVERTER_ADDR)),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("other_basset0000 is not the basset of converter peer_converter0000")
+    );
+
+    deps.querier.set_peer_native_denom("other_denom");
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        set_pair(MOCK_PEER_BASSET_ADDR, Some(MOCK_PEER_CONVERTER_ADDR)),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("equivalent pairs must hold the same native denom")
+    );
+
+    deps.querier
+        .set_peer_native_denom(MOCK_NATIVE_CONTRACT_ADDR);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        set_pair(MOCK_PEER_BASSET_ADDR, Some(MOCK_PEER_CONVERTER_ADDR)),
+    )
+    .unwrap();
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::EquivalentPairs {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let pairs: EquivalentPairsResponse = from_json(&res).unwrap();
+    assert_eq!(
+        pairs.pairs,
+        vec![EquivalentPair {
+            basset_token: MOCK_PEER_BASSET_ADDR.to_string(),
+            converter: MOCK_PEER_CONVERTER_ADDR.to_string(),
+        }]
+    );
+
+    // the reserves go to the equivalent converter, which mints to the sender
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        basset_info.clone(),
+        swap(100000000),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::reply_on_success(
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+                    msg: to_json_binary(&Cw20ExecuteMsg::Burn {
+                        amount: Uint128::new(100000000)
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }),
+                VERIFY_SUPPLY_REPLY_ID
+            ),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_PEER_CONVERTER_ADDR.to_string(),
+                msg: to_json_binary(&ExecuteMsg::MintSwappedBasset {
+                    recipient: sender.to_string(),
+                    min_output: None,
+                    deadline: None,
+                })
+                .unwrap(),
+                funds: vec![Coin::new(10000000000u128, MOCK_NATIVE_CONTRACT_ADDR)],
+            })),
+        ]
+    );
+    assert_eq!(res.events[0].ty, CONVERT_EVENT);
+
+    // swaps are never queued
+    deps.querier
+        .update_balance(vec![Coin::new(100u128, MOCK_NATIVE_CONTRACT_ADDR)]);
+    let error_res = execute(deps.as_mut(), mock_env(), basset_info, swap(100000000)).unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("not enough reserves to swap")
+    );
+
+    // only equivalent converters mint for swapped reserves
+    let mint_swapped = ExecuteMsg::MintSwappedBasset {
+        recipient: sender.to_string(),
+        min_output: None,
+        deadline: None,
+    };
+    let native = [Coin::new(10000000000u128, MOCK_NATIVE_CONTRACT_ADDR)];
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &native),
+        mint_swapped.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+    deps.querier.set_basset_total_supply(Uint128::zero());
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_PEER_CONVERTER_ADDR, &native),
+        mint_swapped.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_on_success(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: sender.to_string(),
+                    amount: Uint128::new(100000000),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            VERIFY_SUPPLY_REPLY_ID
+        )]
+    );
+
+    // a refused swap refunds the user, whose basset the equivalent converter already burned
+    deps.querier
+        .set_basset_total_supply(Uint128::new(1000000000000));
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_PEER_CONVERTER_ADDR, &native),
+        mint_swapped,
+    )
+    .unwrap();
+    assert_eq!(res.attributes, vec![Attribute::new("action", "auto_pause")]);
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: sender.to_string(),
+            amount: native.to_vec(),
+        }))]
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        set_pair(MOCK_PEER_BASSET_ADDR, None),
+    )
+    .unwrap();
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::EquivalentPairs {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let pairs: EquivalentPairsResponse = from_json(&res).unwrap();
+    assert!(pairs.pairs.is_empty());
+}
+
+#[test]
+fn proper_cw20_underlying() {
+    let mut deps = mock_dependencies(&[]);
+    let sender = "addr0000";
+    let convert_cw20 = |recipient: Option<&str>| {
+        Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::new(10000000000),
+            msg: to_json_binary(&Cw20HookMsg::ConvertCw20ToBasset {
+                recipient: recipient.map(|r| r.to_string()),
+                min_output: None,
+                deadline: None,
+            })
+            .unwrap(),
+        })
+    };
+    let redeem = |ibc: Option<IbcTransferInfo>| {
+        Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::new(100000000),
+            msg: to_json_binary(&ConvertBassetToNative {
+                ibc,
+                min_output: None,
+                deadline: None,
+            })
+            .unwrap(),
+        })
+    };
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        default_init(),
+    )
+    .unwrap();
+    // the underlying has the native decimals of the mock querier
+    deps.querier.set_decimals(6, 8);
+    let register = ExecuteMsg::RegisterCw20Tokens {
+        basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+        underlying_token: MOCK_UNDERLYING_TOKEN_ADDR.to_string(),
+    };
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        register.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        register.clone(),
+    )
+    .unwrap();
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        register,
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("the underlying of this pair is already registered")
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_json(&res).unwrap();
+    assert_eq!(
+        config.native_denom,
+        Some(MOCK_UNDERLYING_TOKEN_ADDR.to_string())
+    );
+    assert!(config.cw20_underlying);
+
+    // deposits only come through the hook of the underlying
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            sender,
+            &[Coin::new(10000000000u128, MOCK_NATIVE_CONTRACT_ADDR)],
+        ),
+        ExecuteMsg::ConvertNativeToBasset {
+            recipient: None,
+            min_output: None,
+            deadline: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err(
+            "the underlying is a cw20 token, send it to the converter to convert"
+        )
+    );
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_BASSET_TOKEN_CONTRACT_ADDR, &[]),
+        convert_cw20(None),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_UNDERLYING_TOKEN_ADDR, &[]),
+        convert_cw20(Some("addr0001")),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_on_success(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: "addr0001".to_string(),
+                    amount: Uint128::new(100000000),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            VERIFY_SUPPLY_REPLY_ID
+        )]
+    );
+
+    // the reserves are the converter's balance of the underlying
+    deps.querier
+        .set_basset_total_supply(Uint128::new(100000000));
+    deps.querier
+        .set_underlying_balance(Uint128::new(10000000000));
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Invariants {}).unwrap();
+    let invariants: InvariantsResponse = from_json(&res).unwrap();
+    assert_eq!(invariants.bank_balance, Uint128::new(10000000000));
+    assert_eq!(invariants.missing_reserves, Uint128::zero());
+
+    // redemptions pay out by cw20 transfer
+    let basset_info = mock_info(MOCK_BASSET_TOKEN_CONTRACT_ADDR, &[]);
+    let res = execute(deps.as_mut(), mock_env(), basset_info.clone(), redeem(None)).unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_UNDERLYING_TOKEN_ADDR.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: sender.to_string(),
+                amount: Uint128::new(10000000000),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))
+    );
+
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        basset_info,
+        redeem(Some(IbcTransferInfo {
+            channel_id: "channel-0".to_string(),
+            receiver: "receiver".to_string(),
+            timeout_seconds: 600,
+        })),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("ibc redemptions need a native underlying")
+    );
+
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::RecoverTokens {
+            denom_or_cw20: DenomOrCw20::Cw20(MOCK_UNDERLYING_TOKEN_ADDR.to_string()),
+            amount: Uint128::new(1),
+            recipient: Some(sender.to_string()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err(
+            "the registered underlying token backs the basset supply and cannot be recovered"
+        )
+    );
+}
+
+/// Applies the messages of a response to the mock tokenfactory and answers the replies the
+/// sub messages ask for, in the order the chain executes them.
+fn run_tokenfactory_msgs(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    res: Response<TokenFactoryMsg>,
+) -> StdResult<()> {
+    for sub_msg in res.messages {
+        deps.querier
+            .execute_tokenfactory(MOCK_CONTRACT_ADDR, &sub_msg.msg)?;
+        if sub_msg.reply_on == ReplyOn::Success {
+            reply(
+                deps.as_mut(),
+                mock_env(),
+                Reply {
+                    id: sub_msg.id,
+                    result: SubMsgResult::Ok(SubMsgResponse {
+                        events: vec![],
+                        data: None,
+                    }),
+                },
+            )?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn proper_tokenfactory_basset() {
+    let mut deps = mock_dependencies(&[]);
+    let sender = "addr0000";
+    let basset_denom = tokenfactory_denom(MOCK_CONTRACT_ADDR, "bluna");
+    let register = ExecuteMsg::RegisterTokenFactoryTokens {
+        subdenom: "bluna".to_string(),
+        basset_decimals: 6,
+        native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+        denom_decimals: 8,
+    };
+    let redeem = ExecuteMsg::ConvertBassetCoinsToNative {
+        ibc: None,
+        min_output: None,
+        deadline: None,
+    };
+    let supply_reply = || Reply {
+        id: VERIFY_SUPPLY_REPLY_ID,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        default_init(),
+    )
+    .unwrap();
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        register.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+
+    // the converter creates the denom and is the only one that can mint it
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        register.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Custom(
+            TokenFactoryMsg::CreateDenom {
+                subdenom: "bluna".to_string(),
+            }
+        ))]
+    );
+    run_tokenfactory_msgs(&mut deps, res).unwrap();
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        register,
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("the basset of this pair is already registered")
+    );
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        RegisterTokens {
+            basset_token_address: MOCK_BASSET_TOKEN_CONTRACT_ADDR.to_string(),
+            native_denom: MOCK_NATIVE_CONTRACT_ADDR.to_string(),
+            denom_decimals: 8,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("the basset of this pair is already registered")
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_json(&res).unwrap();
+    assert_eq!(config.basset_denom, Some(basset_denom.clone()));
+    assert_eq!(config.basset_token_address, None);
+
+    // conversions mint coins to the converter and send them on
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            sender,
+            &[Coin::new(10000000000u128, MOCK_NATIVE_CONTRACT_ADDR)],
+        ),
+        ExecuteMsg::ConvertNativeToBasset {
+            recipient: None,
+            min_output: None,
+            deadline: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::reply_on_success(
+                CosmosMsg::Custom(TokenFactoryMsg::MintTokens {
+                    amount: Coin::new(100000000u128, &basset_denom),
+                }),
+                VERIFY_SUPPLY_REPLY_ID
+            ),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: sender.to_string(),
+                amount: vec![Coin::new(100000000u128, &basset_denom)],
+            })),
+        ]
+    );
+
+    // the supply is checked against the bank supply of the denom, which only the mint moves
+    let error_res = reply(deps.as_mut(), mock_env(), supply_reply()).unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("basset supply is 0 but 100000000 was expected")
+    );
+    run_tokenfactory_msgs(&mut deps, res).unwrap();
+
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::ConvertAndDepositCollateral {
+            custody: "custody0000".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err("collateral deposits need a cw20 basset")
+    );
+
+    // redemptions take the coins as funds and burn them
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[Coin::new(1000000u128, MOCK_NATIVE_CONTRACT_ADDR)]),
+        redeem.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err(format!(
+            "send exactly one coin of {} to convert",
+            basset_denom
+        ))
+    );
+
+    deps.querier
+        .update_balance(vec![Coin::new(10000000000u128, MOCK_NATIVE_CONTRACT_ADDR)]);
+    let funds = vec![Coin::new(100000000u128, &basset_denom)];
+    deps.querier
+        .execute_tokenfactory(
+            sender,
+            &CosmosMsg::Bank(BankMsg::Send {
+                to_address: MOCK_CONTRACT_ADDR.to_string(),
+                amount: funds.clone(),
+            }),
+        )
+        .unwrap();
+    let res = execute(deps.as_mut(), mock_env(), mock_info(sender, &funds), redeem).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: sender.to_string(),
+                amount: vec![Coin::new(10000000000u128, MOCK_NATIVE_CONTRACT_ADDR)],
+            })),
+            SubMsg::reply_on_success(
+                CosmosMsg::Custom(TokenFactoryMsg::BurnTokens {
+                    amount: Coin::new(100000000u128, &basset_denom),
+                }),
+                VERIFY_SUPPLY_REPLY_ID
+            ),
+        ]
+    );
+    run_tokenfactory_msgs(&mut deps, res).unwrap();
+
+    // basset coins that reached the converter by plain bank send can be recovered or burned
+    deps.querier
+        .execute_tokenfactory(
+            MOCK_CONTRACT_ADDR,
+            &CosmosMsg::Custom(TokenFactoryMsg::MintTokens {
+                amount: Coin::new(1000u128, &basset_denom),
+            }),
+        )
+        .unwrap();
+    let recover = |amount: u128, recipient: Option<&str>| ExecuteMsg::RecoverTokens {
+        denom_or_cw20: DenomOrCw20::Native(basset_denom.clone()),
+        amount: Uint128::new(amount),
+        recipient: recipient.map(|recipient| recipient.to_string()),
+    };
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        recover(1001, Some(sender)),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        StdError::generic_err(
+            "only 1000 basset is recoverable, 0 is escrowed for queued redemptions"
+        )
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        recover(400, Some(sender)),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: sender.to_string(),
+            amount: vec![Coin::new(400u128, &basset_denom)],
+        }))]
+    );
+    run_tokenfactory_msgs(&mut deps, res).unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        recover(600, None),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_on_success(
+            CosmosMsg::Custom(TokenFactoryMsg::BurnTokens {
+                amount: Coin::new(600u128, &basset_denom),
+            }),
+            VERIFY_SUPPLY_REPLY_ID
+        )]
+    );
+    run_tokenfactory_msgs(&mut deps, res).unwrap();
+}
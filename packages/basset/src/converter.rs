@@ -1,8 +1,28 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
+use cosmwasm_bignumber::Decimal256;
+use cosmwasm_std::{Timestamp, Uint128};
 use cw20::Cw20ReceiveMsg;
 
+/// Event emitted for every conversion, with the attributes keyed below.
+pub const CONVERT_EVENT: &str = "basset_convert";
+/// `to_basset` or `to_native`
+pub const CONVERT_DIRECTION_KEY: &str = "direction";
+/// `<native denom>:<basset token address>`
+pub const CONVERT_PAIR_KEY: &str = "pair";
+pub const CONVERT_SENDER_KEY: &str = "sender";
+pub const CONVERT_RECIPIENT_KEY: &str = "recipient";
+pub const CONVERT_INPUT_AMOUNT_KEY: &str = "input_amount";
+pub const CONVERT_OUTPUT_AMOUNT_KEY: &str = "output_amount";
+pub const CONVERT_FEE_KEY: &str = "fee";
+/// part of the input, in input units, too small to convert into one output unit
+pub const CONVERT_DUST_KEY: &str = "dust";
+
+pub const DIRECTION_TO_BASSET: &str = "to_basset";
+pub const DIRECTION_TO_NATIVE: &str = "to_native";
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub owner: String,
@@ -22,8 +42,43 @@ pub enum ExecuteMsg {
         denom_decimals: u8,
     },
 
+    /// Register a pair whose underlying is a cw20 token, its decimals are read from the token.
+    /// Deposits are sent to the converter with the ConvertCw20ToBasset hook.
+    RegisterCw20Tokens {
+        basset_token_address: String,
+        underlying_token: String,
+    },
+
+    /// Register a pair whose basset is a tokenfactory denom created and minted by the converter
+    /// instead of a cw20 token
+    RegisterTokenFactoryTokens {
+        subdenom: String,
+        basset_decimals: u8,
+        native_denom: String,
+        denom_decimals: u8,
+    },
+
     // convert native denom to basset token
-    ConvertNativeToBasset {},
+    ConvertNativeToBasset {
+        /// mint the basset to this address instead of the sender, as done by ibc-hooks memos
+        /// where the sender is a derived intermediate address. If the conversion fails the
+        /// native funds are sent to the recipient instead.
+        recipient: Option<String>,
+        /// fail if less basset would be minted
+        min_output: Option<Uint128>,
+        /// fail if executed after this time
+        deadline: Option<Timestamp>,
+    },
+
+    /// Convert tokenfactory basset sent as funds to native denom
+    ConvertBassetCoinsToNative {
+        /// send the native denom to another chain instead of the basset sender
+        ibc: Option<IbcTransferInfo>,
+        /// fail if less of the native denom would be returned
+        min_output: Option<Uint128>,
+        /// fail if executed after this time
+        deadline: Option<Timestamp>,
+    },
 
     SetOwner {
         new_owner_addr: String,
@@ -31,13 +86,408 @@ pub enum ExecuteMsg {
 
     AcceptOwnership {
     },
+
+    /// Switch who may convert: everyone, only allowlisted or all but denylisted addresses
+    UpdateAccessMode {
+        mode: AccessMode,
+    },
+
+    UpdateAllowlist {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+
+    UpdateDenylist {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+
+    /// Give an address the permissions of a role, only admins can grant roles
+    GrantRole {
+        role: Role,
+        address: String,
+    },
+
+    RevokeRole {
+        role: Role,
+        address: String,
+    },
+
+    /// Stop all conversions, callable by guardians and admins
+    Pause {},
+
+    /// Resume conversions, callable by admins only
+    Unpause {},
+
+    /// Send the native balance exceeding the backing of the basset supply, callable by treasurers
+    SweepSurplus {
+        recipient: String,
+    },
+
+    /// Return assets sent to the converter by mistake, the registered native denom cannot be
+    /// recovered. Basset received by plain transfer is burned when no recipient is given.
+    RecoverTokens {
+        denom_or_cw20: DenomOrCw20,
+        amount: Uint128,
+        recipient: Option<String>,
+    },
+
+    /// Set the custody contract accepting this pair's basset as collateral
+    SetCustody {
+        custody: String,
+    },
+
+    /// Convert native denom to basset and deposit it as collateral in the custody contract
+    /// on behalf of the sender
+    ConvertAndDepositCollateral {
+        custody: String,
+    },
+
+    /// Set the amount of basset one native token converts to, callable by admins and the
+    /// rate oracle
+    UpdateExchangeRate {
+        exchange_rate: Decimal256,
+    },
+
+    /// Set or remove the contract allowed to update the exchange rate
+    SetRateOracle {
+        oracle: Option<String>,
+    },
+
+    /// Pull the exchange rate from an oracle at conversion time, or stop doing so
+    SetPriceFeed {
+        price_feed: Option<PriceFeed>,
+    },
+
+    /// Change how fractional amounts are rounded in conversions
+    UpdateRoundingMode {
+        rounding_mode: RoundingMode,
+    },
+
+    /// Restrict the pair to one conversion direction, or disable both
+    UpdateConversionMode {
+        mode: ConversionMode,
+    },
+
+    /// Receive the native amount of a queued redemption once reserves filled it
+    ClaimRedemption {
+        id: u64,
+    },
+
+    /// Take back the escrowed basset of a queued redemption that was not filled yet
+    CancelRedemption {
+        id: u64,
+    },
+
+    /// Approve the strategy reserves may be allocated to, or remove it once it holds none
+    SetStrategy {
+        strategy: Option<StrategyConfig>,
+    },
+
+    /// Move reserves into the strategy
+    AllocateToStrategy {
+        amount: Uint128,
+    },
+
+    /// Move reserves back from the strategy, callable by operators and treasurers
+    WithdrawFromStrategy {
+        amount: Uint128,
+    },
+
+    /// Send the strategy value above the allocated reserves to the recipient
+    HarvestStrategyYield {
+        recipient: String,
+    },
+
+    /// Hand the admin role to a council of members, or back to the owner and admins
+    SetCouncil {
+        council: Option<Council>,
+    },
+
+    /// Convert the sent native funds for many recipients at once, each gets the basset for
+    /// its native amount and the amounts must add up to the funds
+    BatchConvertNativeToBasset {
+        recipients: Vec<(String, Uint128)>,
+    },
+
+    /// Register the converter of a basset backed by the same native denom as swappable with
+    /// this pair's basset, or remove it with no converter. Both converters register each other.
+    SetEquivalentPair {
+        basset_token: String,
+        converter: Option<String>,
+    },
+
+    /// Mint basset for the reserves an equivalent converter moved over in a swap, callable by
+    /// registered equivalent converters only
+    MintSwappedBasset {
+        recipient: String,
+        /// fail if less basset would be minted
+        min_output: Option<Uint128>,
+        /// fail if executed after this time
+        deadline: Option<Timestamp>,
+    },
+
+    /// Propose an admin action to the council, the proposer approves it
+    Propose {
+        action: Box<ExecuteMsg>,
+    },
+
+    Approve {
+        id: u64,
+    },
+
+    Reject {
+        id: u64,
+    },
+
+    /// Execute an approved proposal before it expires
+    Execute {
+        id: u64,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Council {
+    pub members: Vec<String>,
+    /// approvals needed to execute a proposal
+    pub threshold: u32,
+    /// seconds a proposal stays open
+    pub voting_period: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalStatus {
+    Open,
+    /// approved by the threshold and ready to execute
+    Passed,
+    Rejected,
+    Executed,
+    /// past the voting period or made under a replaced council
+    Expired,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StrategyConfig {
+    pub strategy: String,
+    /// largest fraction of the reserves that may be allocated to the strategy
+    pub max_allocation: Decimal256,
+    /// native amount that always stays in the converter for instant redemptions
+    pub liquidity_buffer: Uint128,
+}
+
+/// Execute interface of reserve strategies, deposits come with the native funds
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StrategyExecuteMsg {
+    Deposit {},
+    /// send the amount back to the converter
+    Withdraw { amount: Uint128 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StrategyQueryMsg {
+    /// native amount the converter could withdraw
+    TotalValue {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StrategyTotalValueResponse {
+    pub total_value: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PriceFeed {
+    pub oracle: String,
+    /// the oracle price of base in quote is used as the exchange rate, so base is the
+    /// native denom and quote the asset the basset is denominated in
+    pub base: String,
+    pub quote: String,
+    /// oldest accepted price in seconds
+    pub max_age: u64,
+    /// largest accepted change relative to the last accepted exchange rate
+    pub max_deviation: Decimal256,
+}
+
+/// Query message of the price oracle used by price feeds
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OracleQueryMsg {
+    Price { base: String, quote: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OraclePriceResponse {
+    pub rate: Decimal256,
+    pub last_updated_base: u64,
+    pub last_updated_quote: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DenomOrCw20 {
+    Native(String),
+    Cw20(String),
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// manages ownership, registered tokens and roles
+    Admin,
+    /// manages the conversion access mode and lists, can withdraw strategy reserves
+    Operator,
+    /// can pause conversions
+    Guardian,
+    /// can sweep surplus reserves
+    Treasurer,
+}
+
+impl Role {
+    pub const ALL: [Role; 4] = [Role::Admin, Role::Operator, Role::Guardian, Role::Treasurer];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::Operator => "operator",
+            Role::Guardian => "guardian",
+            Role::Treasurer => "treasurer",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessMode {
+    /// any address can convert
+    #[default]
+    Open,
+    /// only addresses on the allowlist can convert
+    Allowlist,
+    /// any address except the ones on the denylist can convert
+    Denylist,
+}
+
+impl fmt::Display for AccessMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AccessMode::Open => write!(f, "open"),
+            AccessMode::Allowlist => write!(f, "allowlist"),
+            AccessMode::Denylist => write!(f, "denylist"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    /// round the output down, in the converter's favor for both directions
+    #[default]
+    Floor,
+    /// round the output up
+    Ceil,
+    /// round to the nearest amount, ties to even
+    HalfEven,
+}
+
+impl fmt::Display for RoundingMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RoundingMode::Floor => write!(f, "floor"),
+            RoundingMode::Ceil => write!(f, "ceil"),
+            RoundingMode::HalfEven => write!(f, "half_even"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConversionMode {
+    /// native can be converted to basset and back
+    #[default]
+    Both,
+    /// only native to basset, e.g. during an incident on the native side
+    MintOnly,
+    /// only basset to native, e.g. when sunsetting the pair
+    RedeemOnly,
+    Disabled,
+}
+
+impl ConversionMode {
+    pub fn can_mint(&self) -> bool {
+        matches!(self, ConversionMode::Both | ConversionMode::MintOnly)
+    }
+
+    pub fn can_redeem(&self) -> bool {
+        matches!(self, ConversionMode::Both | ConversionMode::RedeemOnly)
+    }
+}
+
+impl fmt::Display for ConversionMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConversionMode::Both => write!(f, "both"),
+            ConversionMode::MintOnly => write!(f, "mint_only"),
+            ConversionMode::RedeemOnly => write!(f, "redeem_only"),
+            ConversionMode::Disabled => write!(f, "disabled"),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Cw20HookMsg {
     // convert basset to native denom
-    ConvertBassetToNative {},
+    ConvertBassetToNative {
+        /// send the native denom to another chain instead of the basset sender
+        ibc: Option<IbcTransferInfo>,
+        /// fail if less of the native denom would be returned
+        min_output: Option<Uint128>,
+        /// fail if executed after this time
+        deadline: Option<Timestamp>,
+    },
+    /// convert a cw20 underlying token to basset, sent by the underlying token contract
+    ConvertCw20ToBasset {
+        /// mint the basset to this address instead of the sender
+        recipient: Option<String>,
+        /// fail if less basset would be minted
+        min_output: Option<Uint128>,
+        /// fail if executed after this time
+        deadline: Option<Timestamp>,
+    },
+    /// burn the basset and mint the basset of a registered equivalent pair for its reserves
+    SwapBasset {
+        target_basset: String,
+        /// fail if less of the target basset would be minted
+        min_output: Option<Uint128>,
+        /// fail if executed after this time
+        deadline: Option<Timestamp>,
+    },
+}
+
+/// Transfer of a redemption to another chain, refunded to the basset sender if it fails or
+/// times out. Needs the ibc-hooks module for the callback.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IbcTransferInfo {
+    pub channel_id: String,
+    /// address on the counterparty chain
+    pub receiver: String,
+    /// seconds after the current block time before the transfer times out
+    pub timeout_seconds: u64,
+}
+
+/// Cw20 hook message of the Kryptonite custody contracts
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CustodyCw20HookMsg {
+    DepositCollateral { borrower: Option<String> },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -45,6 +495,51 @@ pub enum Cw20HookMsg {
 pub enum QueryMsg {
     Config {},
     NewOwner {},
+    AccessMode {},
+    Allowlist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    Denylist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    Roles {},
+    ExchangeRate {},
+    /// basset minted for a native amount at the last accepted exchange rate
+    SimulateConvertToBasset {
+        amount: Uint128,
+    },
+    /// native amount returned for a basset amount at the last accepted exchange rate
+    SimulateConvertToNative {
+        amount: Uint128,
+    },
+    RedemptionTicket {
+        id: u64,
+    },
+    RedemptionTickets {
+        owner: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    Strategy {},
+    Invariants {},
+    Council {},
+    Proposal {
+        id: u64,
+    },
+    Proposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    AdminLog {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    EquivalentPairs {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -52,6 +547,121 @@ pub struct ConfigResponse {
     pub owner: String,
     pub native_denom: Option<String>,
     pub basset_token_address: Option<String>,
+    pub custody_contract: Option<String>,
+    pub paused: bool,
+    pub rounding_mode: RoundingMode,
+    pub mode: ConversionMode,
+    /// the native denom is the address of a cw20 underlying token
+    pub cw20_underlying: bool,
+    /// tokenfactory denom of the basset, set instead of the basset token address
+    pub basset_denom: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulationResponse {
+    pub amount: Uint128,
+    pub rounding_mode: RoundingMode,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RedemptionTicketResponse {
+    pub id: u64,
+    pub owner: String,
+    pub basset_amount: Uint128,
+    pub native_amount: Uint128,
+    pub filled: bool,
+    /// 1-based position among the tickets still waiting for reserves
+    pub queue_position: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RedemptionTicketsResponse {
+    pub tickets: Vec<RedemptionTicketResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StrategyResponse {
+    pub strategy: Option<StrategyConfig>,
+    /// reserves allocated to the strategy, part of the basset backing
+    pub principal: Uint128,
+    pub total_value: Uint128,
+    /// strategy value above the principal, not part of the basset backing
+    pub accrued_yield: Uint128,
+}
+
+/// Recorded backing against the actual reserves and the basset supply, all native amounts
+/// except the supply.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InvariantsResponse {
+    pub backing: Uint128,
+    /// converter balance of the underlying, a cw20 balance for cw20 underlying pairs
+    pub bank_balance: Uint128,
+    pub strategy_principal: Uint128,
+    pub basset_supply: Uint128,
+    /// native amount needed to redeem the whole supply
+    pub required_backing: Uint128,
+    /// reserves above the backing, such as donations and rounding dust
+    pub unaccounted_reserves: Uint128,
+    /// backing the reserves do not cover
+    pub missing_reserves: Uint128,
+    /// supply the backing does not cover, for example basset minted outside the converter
+    pub unbacked_supply: Uint128,
+    /// native value conversions rounded up gave away, which the backing check tolerates
+    pub rounding_deficit: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CouncilResponse {
+    pub council: Option<Council>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalResponse {
+    pub id: u64,
+    pub proposer: String,
+    pub action: ExecuteMsg,
+    pub approvals: Vec<String>,
+    pub rejections: Vec<String>,
+    pub expires_at: u64,
+    pub status: ProposalStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalsResponse {
+    pub proposals: Vec<ProposalResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AdminLogEntryResponse {
+    pub id: u64,
+    pub sender: String,
+    pub action: String,
+    /// json encoded value before the change
+    pub old_value: String,
+    /// json encoded value after the change
+    pub new_value: String,
+    /// block time in seconds
+    pub time: u64,
+    /// council proposal the action was executed through
+    pub proposal_id: Option<u64>,
+    /// council members that approved the proposal
+    pub approvers: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AdminLogResponse {
+    pub entries: Vec<AdminLogEntryResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EquivalentPair {
+    pub basset_token: String,
+    pub converter: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EquivalentPairsResponse {
+    pub pairs: Vec<EquivalentPair>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -59,6 +669,59 @@ pub struct NewOwnerResponse {
     pub new_owner: String,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AccessModeResponse {
+    pub mode: AccessMode,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AddressListResponse {
+    pub addresses: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoleMembers {
+    pub role: Role,
+    pub addresses: Vec<String>,
+}
+
+/// The owner implicitly holds every role and is not listed here
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RolesResponse {
+    pub roles: Vec<RoleMembers>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExchangeRateResponse {
+    /// last accepted exchange rate, used by conversions
+    pub exchange_rate: Decimal256,
+    /// time of the oracle price the exchange rate was taken from
+    pub last_updated: Option<u64>,
+    pub oracle: Option<String>,
+    pub price_feed: Option<PriceFeed>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct MigrateMsg {}
+
+/// Callbacks of the ibc-hooks module for transfers sent with an `ibc_callback` memo
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SudoMsg {
+    IbcLifecycleComplete(IbcLifecycleComplete),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IbcLifecycleComplete {
+    IbcAck {
+        channel: String,
+        sequence: u64,
+        ack: String,
+        /// false for an error acknowledgement, the transferred coins were refunded
+        success: bool,
+    },
+    /// the transfer timed out and the transferred coins were refunded
+    IbcTimeout { channel: String, sequence: u64 },
+}
@@ -3,9 +3,14 @@ pub mod converter;
 pub mod reward;
 pub mod common;
 pub mod querier;
+pub mod tokenfactory;
 
 #[cfg(test)]
 pub mod mock_querier;
 
+// shared with the tests of the contracts that use tokenfactory coins
+#[cfg(not(target_arch = "wasm32"))]
+pub mod mock_tokenfactory;
+
 #[cfg(test)]
 mod testing;
@@ -0,0 +1,90 @@
+use cosmwasm_std::{BankMsg, CosmosMsg, StdError, StdResult, Uint128};
+use std::collections::HashMap;
+
+use crate::tokenfactory::{tokenfactory_denom, TokenFactoryMsg};
+
+/// Applies tokenfactory and bank messages to in-memory balances the way the chain does.
+#[derive(Default)]
+pub struct MockTokenFactory {
+    // creator of each denom
+    creators: HashMap<String, String>,
+    // balance of each address and denom
+    balances: HashMap<(String, String), Uint128>,
+}
+
+impl MockTokenFactory {
+    pub fn execute(&mut self, sender: &str, msg: &CosmosMsg<TokenFactoryMsg>) -> StdResult<()> {
+        match msg {
+            CosmosMsg::Custom(TokenFactoryMsg::CreateDenom { subdenom }) => {
+                let denom = tokenfactory_denom(sender, subdenom);
+                if self.creators.contains_key(&denom) {
+                    return Err(StdError::generic_err(format!(
+                        "denom {} already exists",
+                        denom
+                    )));
+                }
+                self.creators.insert(denom, sender.to_string());
+                Ok(())
+            }
+            CosmosMsg::Custom(TokenFactoryMsg::MintTokens { amount }) => {
+                self.assert_creator(sender, &amount.denom)?;
+                self.add(sender, &amount.denom, amount.amount)
+            }
+            CosmosMsg::Custom(TokenFactoryMsg::BurnTokens { amount }) => {
+                self.assert_creator(sender, &amount.denom)?;
+                self.sub(sender, &amount.denom, amount.amount)
+            }
+            CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+                for coin in amount {
+                    self.sub(sender, &coin.denom, coin.amount)?;
+                    self.add(to_address, &coin.denom, coin.amount)?;
+                }
+                Ok(())
+            }
+            _ => Err(StdError::generic_err("unsupported message")),
+        }
+    }
+
+    pub fn balance(&self, address: &str, denom: &str) -> Uint128 {
+        self.balances
+            .get(&(address.to_string(), denom.to_string()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn supply(&self, denom: &str) -> Uint128 {
+        self.balances
+            .iter()
+            .filter(|((_, d), _)| d == denom)
+            .map(|(_, amount)| *amount)
+            .sum()
+    }
+
+    fn assert_creator(&self, sender: &str, denom: &str) -> StdResult<()> {
+        if self.creators.get(denom).map(|c| c.as_str()) != Some(sender) {
+            return Err(StdError::generic_err(format!(
+                "{} is not the creator of {}",
+                sender, denom
+            )));
+        }
+        Ok(())
+    }
+
+    fn add(&mut self, address: &str, denom: &str, amount: Uint128) -> StdResult<()> {
+        let balance = self
+            .balances
+            .entry((address.to_string(), denom.to_string()))
+            .or_default();
+        *balance = balance.checked_add(amount)?;
+        Ok(())
+    }
+
+    fn sub(&mut self, address: &str, denom: &str, amount: Uint128) -> StdResult<()> {
+        let balance = self
+            .balances
+            .entry((address.to_string(), denom.to_string()))
+            .or_default();
+        *balance = balance.checked_sub(amount)?;
+        Ok(())
+    }
+}
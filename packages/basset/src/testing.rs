@@ -1,6 +1,8 @@
 use crate::querier::deduct_tax;
 use crate::mock_querier::mock_dependencies;
-use cosmwasm_std::{Coin, Decimal, Uint128};
+use crate::mock_tokenfactory::MockTokenFactory;
+use crate::tokenfactory::{tokenfactory_denom, TokenFactoryMsg};
+use cosmwasm_std::{to_json_string, BankMsg, Coin, CosmosMsg, Decimal, StdError, Uint128};
 
 #[test]
 fn test_deduct_tax() {
@@ -29,3 +31,61 @@ fn test_deduct_tax() {
         }
     );
 }
+
+#[test]
+fn test_tokenfactory_messages() {
+    let creator = "converter0000";
+    let denom = tokenfactory_denom(creator, "bstable");
+    assert_eq!(denom, "factory/converter0000/bstable");
+
+    // the messages serialize like the custom messages of the chain
+    let mint: CosmosMsg<TokenFactoryMsg> = TokenFactoryMsg::MintTokens {
+        amount: Coin::new(100u128, denom.clone()),
+    }
+    .into();
+    assert_eq!(
+        to_json_string(&mint).unwrap(),
+        r#"{"custom":{"mint_tokens":{"amount":{"denom":"factory/converter0000/bstable","amount":"100"}}}}"#
+    );
+
+    let mut tokenfactory = MockTokenFactory::default();
+    let create = TokenFactoryMsg::CreateDenom {
+        subdenom: "bstable".to_string(),
+    }
+    .into();
+    tokenfactory.execute(creator, &create).unwrap();
+    assert_eq!(
+        tokenfactory.execute(creator, &create).unwrap_err(),
+        StdError::generic_err("denom factory/converter0000/bstable already exists")
+    );
+
+    // only the creator mints, to itself, and sends the coins on
+    assert_eq!(
+        tokenfactory.execute("addr0000", &mint).unwrap_err(),
+        StdError::generic_err("addr0000 is not the creator of factory/converter0000/bstable")
+    );
+    tokenfactory.execute(creator, &mint).unwrap();
+    tokenfactory
+        .execute(
+            creator,
+            &CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr0000".to_string(),
+                amount: vec![Coin::new(60u128, denom.clone())],
+            }),
+        )
+        .unwrap();
+    assert_eq!(tokenfactory.balance("addr0000", &denom), Uint128::new(60));
+    assert_eq!(tokenfactory.supply(&denom), Uint128::new(100));
+
+    // burned coins come from the creator balance
+    let burn = |amount: u128| -> CosmosMsg<TokenFactoryMsg> {
+        TokenFactoryMsg::BurnTokens {
+            amount: Coin::new(amount, denom.clone()),
+        }
+        .into()
+    };
+    tokenfactory.execute(creator, &burn(50)).unwrap_err();
+    tokenfactory.execute(creator, &burn(30)).unwrap();
+    assert_eq!(tokenfactory.balance(creator, &denom), Uint128::new(10));
+    assert_eq!(tokenfactory.supply(&denom), Uint128::new(70));
+}
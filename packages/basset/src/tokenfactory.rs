@@ -0,0 +1,28 @@
+use cosmwasm_std::{Coin, CosmosMsg, CustomMsg};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Tokenfactory messages in the format of the Sei custom messages. Only the creator of a
+/// denom can mint and burn it, minted coins go to the creator and burned coins are taken
+/// from its balance.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenFactoryMsg {
+    /// create the denom `factory/<creator>/<subdenom>`
+    CreateDenom { subdenom: String },
+    MintTokens { amount: Coin },
+    BurnTokens { amount: Coin },
+}
+
+impl CustomMsg for TokenFactoryMsg {}
+
+impl From<TokenFactoryMsg> for CosmosMsg<TokenFactoryMsg> {
+    fn from(msg: TokenFactoryMsg) -> CosmosMsg<TokenFactoryMsg> {
+        CosmosMsg::Custom(msg)
+    }
+}
+
+/// Denom created by a CreateDenom message of the creator.
+pub fn tokenfactory_denom(creator: &str, subdenom: &str) -> String {
+    format!("factory/{}/{}", creator, subdenom)
+}
@@ -24,10 +24,10 @@ pub fn execute_increase_allowance(
         deps.storage,
         (
             deps.api
-                .addr_canonicalize(&info.sender.to_string())?
+                .addr_canonicalize(info.sender.as_str())?
                 .as_slice(),
             deps.api
-                .addr_canonicalize(&spender_addr.to_string())?
+                .addr_canonicalize(spender_addr.as_str())?
                 .as_slice(),
         ),
         |allow| -> StdResult<_> {
@@ -101,8 +101,8 @@ pub fn deduct_allowance(
     ALLOWANCES.update(
         storage,
         (
-            api.addr_canonicalize(&owner.to_string())?.as_slice(),
-            api.addr_canonicalize(&spender.to_string())?.as_slice(),
+            api.addr_canonicalize(owner.as_str())?.as_slice(),
+            api.addr_canonicalize(spender.as_str())?.as_slice(),
         ),
         |current| {
             match current {
@@ -148,7 +148,7 @@ pub fn execute_transfer_from(
     BALANCES.update(
         deps.storage,
         deps.api
-            .addr_canonicalize(&owner_addr.to_string())?
+            .addr_canonicalize(owner_addr.as_str())?
             .as_slice(),
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
@@ -157,7 +157,7 @@ pub fn execute_transfer_from(
     BALANCES.update(
         deps.storage,
         deps.api
-            .addr_canonicalize(&rcpt_addr.to_string())?
+            .addr_canonicalize(rcpt_addr.as_str())?
             .as_slice(),
         |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
     )?;
@@ -195,7 +195,7 @@ pub fn execute_burn_from(
     BALANCES.update(
         deps.storage,
         deps.api
-            .addr_canonicalize(&owner_addr.to_string())?
+            .addr_canonicalize(owner_addr.as_str())?
             .as_slice(),
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
@@ -241,7 +241,7 @@ pub fn execute_send_from(
     BALANCES.update(
         deps.storage,
         deps.api
-            .addr_canonicalize(&owner_addr.to_string())?
+            .addr_canonicalize(owner_addr.as_str())?
             .as_slice(),
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
@@ -250,7 +250,7 @@ pub fn execute_send_from(
     BALANCES.update(
         deps.storage,
         deps.api
-            .addr_canonicalize(&rcpt_addr.to_string())?
+            .addr_canonicalize(rcpt_addr.as_str())?
             .as_slice(),
         |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
     )?;
@@ -129,7 +129,7 @@ pub fn execute_transfer(
     BALANCES.update(
         deps.storage,
         deps.api
-            .addr_canonicalize(&info.sender.to_string())?
+            .addr_canonicalize(info.sender.as_str())?
             .as_slice(),
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
@@ -138,7 +138,7 @@ pub fn execute_transfer(
     BALANCES.update(
         deps.storage,
         deps.api
-            .addr_canonicalize(&rcpt_addr.to_string())?
+            .addr_canonicalize(rcpt_addr.as_str())?
             .as_slice(),
         |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
     )?;
@@ -165,7 +165,7 @@ pub fn execute_burn(
     BALANCES.update(
         deps.storage,
         deps.api
-            .addr_canonicalize(&info.sender.to_string())?
+            .addr_canonicalize(info.sender.as_str())?
             .as_slice(),
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
@@ -198,7 +198,7 @@ pub fn execute_mint(
     let mut config = TOKEN_INFO.load(deps.storage)?;
     if config.mint.is_none()
         || config.mint.as_ref().unwrap().minter
-            != deps.api.addr_canonicalize(&info.sender.to_string())?
+            != deps.api.addr_canonicalize(info.sender.as_str())?
     {
         return Err(ContractError::Unauthorized {});
     }
@@ -217,7 +217,7 @@ pub fn execute_mint(
     BALANCES.update(
         deps.storage,
         deps.api
-            .addr_canonicalize(&rcpt_addr.to_string())?
+            .addr_canonicalize(rcpt_addr.as_str())?
             .as_slice(),
         |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
     )?;
@@ -247,7 +247,7 @@ pub fn execute_send(
     BALANCES.update(
         deps.storage,
         deps.api
-            .addr_canonicalize(&info.sender.to_string())?
+            .addr_canonicalize(info.sender.as_str())?
             .as_slice(),
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
@@ -256,7 +256,7 @@ pub fn execute_send(
     BALANCES.update(
         deps.storage,
         deps.api
-            .addr_canonicalize(&rcpt_addr.to_string())?
+            .addr_canonicalize(rcpt_addr.as_str())?
             .as_slice(),
         |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
     )?;
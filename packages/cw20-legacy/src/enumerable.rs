@@ -295,16 +295,16 @@ mod tests {
     const PREFIX_ALLOWANCE: &[u8] = b"allowance";
 
     /// balances are state of the erc20 tokens
-    pub fn legacy_balances(storage: &mut dyn Storage) -> Bucket<Uint128> {
+    pub fn legacy_balances(storage: &mut dyn Storage) -> Bucket<'_, Uint128> {
         bucket(storage, PREFIX_BALANCE)
     }
 
     /// balances are state of the erc20 tokens (read-only version for queries)
-    pub fn legacy_balances_read(storage: &dyn Storage) -> ReadonlyBucket<Uint128> {
+    pub fn legacy_balances_read(storage: &dyn Storage) -> ReadonlyBucket<'_, Uint128> {
         bucket_read(storage, PREFIX_BALANCE)
     }
 
-    pub fn legacy_balances_prefix_read(storage: &dyn Storage) -> ReadonlyPrefixedStorage {
+    pub fn legacy_balances_prefix_read(storage: &dyn Storage) -> ReadonlyPrefixedStorage<'_> {
         ReadonlyPrefixedStorage::new(storage, PREFIX_BALANCE)
     }
 